@@ -5,6 +5,8 @@ import sys
 import pandas as pd
 
 result = sys.argv[1] if len(sys.argv) > 1 else "./result.txt"
+# Optional reference file of the instance, e.g. data/001/reference.txt.
+reference = sys.argv[2] if len(sys.argv) > 2 else None
 df = pd.read_csv(result, sep=" ", header=None)
 
 print("Min:", df[0].min())
@@ -13,3 +15,9 @@ print("Standard deviation (cost):", df[0].std())
 print(f"Avg time: {df[1].mean()}s")
 print("Standard deviation (time):", df[1].std())
 print("Count:", len(df))
+if reference:
+    with open(reference) as f:
+        value = float(f.read().split()[1])
+    gap = 100 * (df[0] - value) / value
+    print(f"Min gap: {gap.min()}%")
+    print(f"Avg gap: {gap.mean()}%")
@@ -3,9 +3,10 @@
 DURATION=60
 END_TIME=$(($(date +%s) + $DURATION))
 TEMP_DIR=/tmp/grasp
+BIN=${BIN:-memetic}
 RESULT=${1:-result.txt}
 if [ -n "$1" ]; then shift 1; fi
-PARAMS=${@:-"100 100 0.01"}
+PARAMS=${@:-"data/012/data.csv 100 100 0.01"}
 
 rm -rf $TEMP_DIR
 mkdir -p $TEMP_DIR
@@ -13,8 +14,10 @@ mkdir -p $TEMP_DIR
 for i in $(seq $(nproc)); do
     TEMP_RESULT=$TEMP_DIR/result_$i.txt
     while [ $(date +%s) -lt $END_TIME ]; do
-        # TODO: change this to the desired bin depending on the algorithm
-        ./target/release/memetic $PARAMS >> $TEMP_RESULT
+        # The algorithm is chosen with e.g. `BIN=grasp ./run.sh result.txt data/001/data.csv 100 0.2`
+        # Each line is `cost time seed`, so that a run can be reproduced with `SEED=<seed>`.
+        SEED=$(od -An -N8 -tu8 /dev/urandom | tr -d ' ')
+        echo "$(SEED=$SEED ./target/release/$BIN $PARAMS) $SEED" >> $TEMP_RESULT
     done &
 done
 
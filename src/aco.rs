@@ -0,0 +1,242 @@
+//! Otimização por Colônia de Formigas (*Ant Colony Optimization*, ACO) para o Problema do
+//! Caixeiro Viajante, nas variantes *Ant System* e *MAX-MIN Ant System*.
+//!
+//! Funciona sobre qualquer [`DistanceMatrix`], i.e., tanto sobre as matrizes `[[f64; N]; N]`
+//! geradas por `graph_from_csv!` quanto sobre a [`crate::matrix::Matrix`] carregada em tempo de
+//! execução.
+
+use std::{fmt, str::FromStr, time::Instant};
+
+use rand::Rng;
+
+use crate::{
+    budget::Budget,
+    heuristics::nearest_neighbour,
+    local_search::{
+        Solution,
+        vnd::{CandidateLists, Neighbourhood, vnd},
+    },
+    matrix::DistanceMatrix,
+};
+
+/// Probabilidade de uma formiga reconstruir a melhor rota ao convergir, usada no cálculo do
+/// limite inferior de feromônio do MAX-MIN Ant System.
+const P_BEST: f64 = 0.05;
+
+/// Variante do algoritmo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Variant {
+    /// Todas as formigas depositam feromônio, proporcional à qualidade das suas rotas.
+    AntSystem,
+    /// Apenas a melhor rota deposita feromônio, que fica limitado ao intervalo
+    /// `[tau_min, tau_max]`.
+    MaxMin,
+}
+
+impl FromStr for Variant {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "as" => Ok(Variant::AntSystem),
+            "mmas" => Ok(Variant::MaxMin),
+            s => Err(format!("unknown ACO variant: {s}")),
+        }
+    }
+}
+
+impl fmt::Display for Variant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Variant::AntSystem => write!(f, "ant system"),
+            Variant::MaxMin => write!(f, "max-min ant system"),
+        }
+    }
+}
+
+/// Parâmetros da colônia.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Aco {
+    pub variant: Variant,
+    /// Quantidade de formigas por iteração.
+    pub ants: usize,
+    /// Peso do feromônio na escolha do próximo vértice.
+    pub alpha: f64,
+    /// Peso da visibilidade `1/d` na escolha do próximo vértice.
+    pub beta: f64,
+    /// Taxa de evaporação do feromônio, entre 0 e 1.
+    pub rho: f64,
+    /// Buscas locais aplicadas, via [`vnd`], à rota de cada formiga. Vazio para nenhuma.
+    pub local_search: Vec<Neighbourhood>,
+    /// Critério de parada, em quantidade de iterações ou tempo.
+    pub budget: Budget,
+}
+
+/// Matriz de feromônio e as informações usadas na construção das rotas.
+struct Colony<'a, M: DistanceMatrix + ?Sized> {
+    graph: &'a M,
+    n: usize,
+    symmetric: bool,
+    pheromone: Vec<f64>,
+    /// `d(u, v)^(−beta)`, pré-calculado.
+    visibility: Vec<f64>,
+}
+
+impl<'a, M: DistanceMatrix + ?Sized> Colony<'a, M> {
+    fn new(graph: &'a M, beta: f64, tau0: f64) -> Self {
+        let n = graph.node_count();
+        let visibility = (0..n * n)
+            .map(|k| (1.0 / graph.cost(k / n, k % n).max(f64::EPSILON)).powf(beta))
+            .collect();
+
+        Self {
+            graph,
+            n,
+            symmetric: graph.is_symmetric(),
+            pheromone: vec![tau0; n * n],
+            visibility,
+        }
+    }
+
+    /// Constrói a rota de uma formiga a partir de um vértice sorteado. O próximo vértice é
+    /// sorteado por roleta, com probabilidade proporcional a `tau(u, v)^alpha · (1/d(u, v))^beta`.
+    fn tour<R: Rng + ?Sized>(&self, alpha: f64, rng: &mut R) -> Solution {
+        let n = self.n;
+        let mut visited = vec![false; n];
+        let mut route = Vec::with_capacity(n);
+        let mut weights = vec![0.0; n];
+        let start = rng.random_range(0..n);
+        route.push(start);
+        visited[start] = true;
+
+        for _ in 1..n {
+            let u = route[route.len() - 1];
+            let mut total = 0.0;
+            for v in 0..n {
+                weights[v] = if visited[v] {
+                    0.0
+                } else {
+                    self.pheromone[u * n + v].powf(alpha) * self.visibility[u * n + v]
+                };
+                total += weights[v];
+            }
+
+            let mut threshold = rng.random::<f64>() * total;
+            let next = (0..n)
+                .filter(|&v| !visited[v])
+                .find(|&v| {
+                    threshold -= weights[v];
+                    threshold <= 0.0
+                })
+                .or_else(|| (0..n).rev().find(|&v| !visited[v]))
+                .expect("No vertex left to visit");
+
+            visited[next] = true;
+            route.push(next);
+        }
+
+        let cost = Solution::calculate_cost(&route, self.graph);
+        Solution { route, cost }
+    }
+
+    fn evaporate(&mut self, rho: f64) {
+        for tau in &mut self.pheromone {
+            *tau *= 1.0 - rho;
+        }
+    }
+
+    /// Deposita `amount` de feromônio nas arestas da rota de `solution`.
+    fn deposit(&mut self, solution: &Solution, amount: f64) {
+        let n = self.n;
+        let route = &solution.route;
+        for k in 0..n {
+            let (u, v) = (route[k], route[(k + 1) % n]);
+            self.pheromone[u * n + v] += amount;
+            if self.symmetric {
+                self.pheromone[v * n + u] += amount;
+            }
+        }
+    }
+
+    fn clamp(&mut self, min: f64, max: f64) {
+        for tau in &mut self.pheromone {
+            *tau = tau.clamp(min, max);
+        }
+    }
+}
+
+impl Aco {
+    /// Limites `[tau_min, tau_max]` do MAX-MIN Ant System para uma melhor rota de custo
+    /// `best_cost`, conforme Stützle e Hoos (2000).
+    fn trail_limits(&self, n: usize, best_cost: f64) -> (f64, f64) {
+        let max = 1.0 / (self.rho * best_cost);
+        let root = P_BEST.powf(1.0 / n as f64);
+        let min = max * (1.0 - root) / ((n as f64 / 2.0 - 1.0).max(1.0) * root);
+        (min.min(max), max)
+    }
+
+    /// Executa a colônia e retorna a melhor rota encontrada.
+    ///
+    /// # Detalhes de funcionamento
+    /// 1. Inicializa o feromônio a partir do custo `C` da rota do vizinho mais próximo: `m / C` no
+    ///    Ant System e `tau_max` no MAX-MIN;
+    /// 2. Em cada iteração, cada uma das `m` formigas constrói uma rota e, se houver, aplica a busca
+    ///    local sobre ela;
+    /// 3. O feromônio evapora à taxa `rho`. No Ant System, cada formiga deposita `1/L` nas arestas
+    ///    da sua rota de custo `L`; no MAX-MIN, apenas a melhor rota encontrada até então deposita,
+    ///    e o feromônio é limitado a `[tau_min, tau_max]`, recalculados sempre que ela melhora;
+    /// 4. Repete até o fim do orçamento.
+    pub fn run<M, R>(&self, graph: &M, rng: &mut R) -> Solution
+    where
+        M: DistanceMatrix + ?Sized,
+        R: Rng + ?Sized,
+    {
+        let n = graph.node_count();
+        let mut best = nearest_neighbour(graph, 0);
+        if n < 3 {
+            return best;
+        }
+
+        let (mut tau_min, mut tau_max) = self.trail_limits(n, best.cost);
+        let tau0 = match self.variant {
+            Variant::AntSystem => self.ants as f64 / best.cost,
+            Variant::MaxMin => tau_max,
+        };
+        let mut colony = Colony::new(graph, self.beta, tau0);
+        let candidates = CandidateLists::new(graph, &self.local_search);
+        let started = Instant::now();
+        let mut iteration = 0;
+
+        while !self.budget.exhausted(iteration, started) {
+            iteration += 1;
+
+            let tours: Vec<Solution> = (0..self.ants)
+                .map(|_| {
+                    let tour = colony.tour(self.alpha, rng);
+                    vnd(&tour, graph, &self.local_search, &candidates)
+                })
+                .collect();
+            if let Some(iteration_best) = tours.iter().min_by(|a, b| a.cost.total_cmp(&b.cost))
+                && iteration_best.cost < best.cost
+            {
+                best = iteration_best.clone();
+                (tau_min, tau_max) = self.trail_limits(n, best.cost);
+            }
+
+            colony.evaporate(self.rho);
+            match self.variant {
+                Variant::AntSystem => {
+                    for tour in &tours {
+                        colony.deposit(tour, 1.0 / tour.cost);
+                    }
+                }
+                Variant::MaxMin => {
+                    colony.deposit(&best, 1.0 / best.cost);
+                    colony.clamp(tau_min, tau_max);
+                }
+            }
+        }
+
+        best
+    }
+}
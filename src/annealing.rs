@@ -0,0 +1,278 @@
+//! Têmpera Simulada (*Simulated Annealing*) para o Problema do Caixeiro Viajante.
+//!
+//! A vizinhança é formada pelos movimentos de [`crate::local_search::moves`]: a cada passo, um
+//! movimento aleatório de um dos tipos escolhidos é sorteado, avaliado em O(1) e aceito pelo
+//! critério de Metropolis.
+
+use std::{fmt, str::FromStr, time::Instant};
+
+use rand::{Rng, seq::IndexedRandom};
+
+use crate::{
+    budget::Budget,
+    local_search::{
+        Solution,
+        moves::{Move, OrOpt, Swap, TwoOpt},
+    },
+    matrix::DistanceMatrix,
+};
+
+/// Tipo de movimento sorteado pela têmpera simulada.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveKind {
+    Swap,
+    /// Realocação de um único vértice.
+    Shift,
+    TwoOpt,
+    /// Realocação, possivelmente invertida, de uma sequência de até o tamanho dado.
+    OrOpt(usize),
+}
+
+impl MoveKind {
+    /// Lê uma lista de movimentos separados por vírgula, e.g. `swap,2opt`.
+    pub fn parse_list(s: &str) -> Result<Vec<Self>, String> {
+        s.split(',').map(str::parse).collect()
+    }
+
+    /// Sorteia um movimento deste tipo para uma rota com `n >= 4` vértices.
+    fn sample<R: Rng + ?Sized>(self, n: usize, rng: &mut R) -> RandomMove {
+        match self {
+            MoveKind::Swap => {
+                let i = rng.random_range(0..n);
+                let j = (i + rng.random_range(1..n)) % n;
+                RandomMove::Swap(Swap { i, j })
+            }
+            MoveKind::Shift | MoveKind::OrOpt(_) => {
+                let max_len = if let MoveKind::OrOpt(max_len) = self {
+                    max_len.clamp(1, n - 1)
+                } else {
+                    1
+                };
+                let len = rng.random_range(1..=max_len);
+                let start = rng.random_range(0..=n - len);
+                let reversed = len > 1 && rng.random_bool(0.5);
+                let to = if reversed {
+                    rng.random_range(0..=n - len)
+                } else {
+                    (start + rng.random_range(1..=n - len)) % (n - len + 1)
+                };
+                RandomMove::OrOpt(OrOpt {
+                    start,
+                    len,
+                    to,
+                    reversed,
+                })
+            }
+            MoveKind::TwoOpt => {
+                let i = rng.random_range(0..n - 2);
+                let j = rng.random_range(i + 2..n);
+                RandomMove::TwoOpt(TwoOpt { i, j })
+            }
+        }
+    }
+}
+
+impl FromStr for MoveKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "swap" => Ok(MoveKind::Swap),
+            "shift" => Ok(MoveKind::Shift),
+            "2opt" => Ok(MoveKind::TwoOpt),
+            "oropt" => Ok(MoveKind::OrOpt(3)),
+            s => Err(format!("unknown move: {s}")),
+        }
+    }
+}
+
+impl fmt::Display for MoveKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoveKind::Swap => write!(f, "swap"),
+            MoveKind::Shift => write!(f, "shift"),
+            MoveKind::TwoOpt => write!(f, "2-opt"),
+            MoveKind::OrOpt(max_len) => write!(f, "or-opt ({max_len})"),
+        }
+    }
+}
+
+/// Movimento sorteado, de qualquer um dos tipos de [`MoveKind`].
+enum RandomMove {
+    Swap(Swap),
+    OrOpt(OrOpt),
+    TwoOpt(TwoOpt),
+}
+
+impl Move for RandomMove {
+    fn delta<M: DistanceMatrix + ?Sized>(&self, route: &[usize], graph: &M) -> f64 {
+        match self {
+            RandomMove::Swap(mv) => mv.delta(route, graph),
+            RandomMove::OrOpt(mv) => mv.delta(route, graph),
+            RandomMove::TwoOpt(mv) => mv.delta(route, graph),
+        }
+    }
+
+    fn apply(&self, route: &mut Vec<usize>) {
+        match self {
+            RandomMove::Swap(mv) => mv.apply(route),
+            RandomMove::OrOpt(mv) => mv.apply(route),
+            RandomMove::TwoOpt(mv) => mv.apply(route),
+        }
+    }
+
+    fn asymmetric_delta<M: DistanceMatrix + ?Sized>(&self, route: &[usize], graph: &M) -> f64 {
+        match self {
+            RandomMove::Swap(mv) => mv.asymmetric_delta(route, graph),
+            RandomMove::OrOpt(mv) => mv.asymmetric_delta(route, graph),
+            RandomMove::TwoOpt(mv) => mv.asymmetric_delta(route, graph),
+        }
+    }
+}
+
+/// Esquema de resfriamento, aplicado ao fim de cada patamar de temperatura.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Cooling {
+    /// `T ← alpha · T`.
+    Geometric { alpha: f64 },
+    /// `T = T0 · (1 − progresso)`, onde o progresso é a fração consumida do orçamento.
+    Linear,
+    /// Resfriamento geométrico que, após `patience` patamares seguidos sem melhorar a melhor
+    /// solução, reaquece para `reheat · T0`.
+    Adaptive {
+        alpha: f64,
+        patience: usize,
+        reheat: f64,
+    },
+}
+
+/// Estima uma temperatura inicial em que movimentos de piora são aceitos com probabilidade
+/// `acceptance`.
+///
+/// # Detalhes de funcionamento
+/// Sorteia `samples` movimentos de `moves` a partir de `solution` e calcula a média `Δ` das
+/// variações positivas. Como um movimento de piora `Δ` é aceito com probabilidade `e^(−Δ/T)`,
+/// a temperatura é `T0 = −Δ / ln(acceptance)`. Caso nenhum movimento sorteado piore a solução,
+/// retorna `1`.
+pub fn initial_temperature<M, R>(
+    graph: &M,
+    solution: &Solution,
+    moves: &[MoveKind],
+    acceptance: f64,
+    samples: usize,
+    rng: &mut R,
+) -> f64
+where
+    M: DistanceMatrix + ?Sized,
+    R: Rng + ?Sized,
+{
+    let n = solution.route.len();
+    if n < 4 {
+        return 1.0;
+    }
+
+    let symmetric = graph.is_symmetric();
+    let (sum, count) = (0..samples)
+        .filter_map(|_| {
+            let kind = moves.choose(rng).expect("No move kind given");
+            let delta = solution.evaluate(graph, symmetric, &kind.sample(n, rng));
+            (delta > 0.0).then_some(delta)
+        })
+        .fold((0.0, 0), |(sum, count), delta| (sum + delta, count + 1));
+
+    if count == 0 {
+        1.0
+    } else {
+        -(sum / count as f64) / acceptance.ln()
+    }
+}
+
+/// Parâmetros da têmpera simulada.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Annealing {
+    /// Tipos de movimento sorteados a cada passo.
+    pub moves: Vec<MoveKind>,
+    pub cooling: Cooling,
+    /// Probabilidade de aceitar uma piora média no início, usada para calibrar a temperatura
+    /// inicial com [`initial_temperature`].
+    pub initial_acceptance: f64,
+    /// Quantidade de movimentos sorteados em cada patamar de temperatura.
+    pub epoch: usize,
+    /// Critério de parada, em quantidade de patamares de temperatura ou tempo.
+    pub budget: Budget,
+}
+
+impl Annealing {
+    /// Executa a têmpera simulada a partir de `initial` e retorna a melhor solução encontrada.
+    ///
+    /// # Detalhes de funcionamento
+    /// 1. Calibra a temperatura inicial `T0` com `100 · n` movimentos sorteados;
+    /// 2. Em cada patamar, sorteia `epoch` movimentos. Um movimento com variação `Δ` é aceito se
+    ///    `Δ < 0` ou com probabilidade `e^(−Δ/T)`;
+    /// 3. Ao fim do patamar, atualiza a temperatura conforme o esquema de resfriamento;
+    /// 4. Repete até o fim do orçamento, retornando a melhor solução visitada.
+    pub fn run<M, R>(&self, initial: &Solution, graph: &M, rng: &mut R) -> Solution
+    where
+        M: DistanceMatrix + ?Sized,
+        R: Rng + ?Sized,
+    {
+        let n = initial.route.len();
+        let mut current = initial.clone();
+        let mut best = initial.clone();
+        if n < 4 {
+            return best;
+        }
+
+        let symmetric = graph.is_symmetric();
+        let t0 = initial_temperature(
+            graph,
+            initial,
+            &self.moves,
+            self.initial_acceptance,
+            100 * n,
+            rng,
+        );
+        let mut temperature = t0;
+        let started = Instant::now();
+        let mut level = 0;
+        let mut stale = 0;
+
+        while !self.budget.exhausted(level, started) {
+            level += 1;
+            let mut improved = false;
+
+            for _ in 0..self.epoch {
+                let kind = self.moves.choose(rng).expect("No move kind given");
+                let mv = kind.sample(n, rng);
+                let delta = current.evaluate(graph, symmetric, &mv);
+
+                if delta < 0.0
+                    || (temperature > 0.0 && rng.random::<f64>() < (-delta / temperature).exp())
+                {
+                    current.apply(&mv, delta);
+                    if current.cost < best.cost {
+                        best = current.clone();
+                        improved = true;
+                    }
+                }
+            }
+
+            stale = if improved { 0 } else { stale + 1 };
+            temperature = match self.cooling {
+                Cooling::Geometric { alpha } => alpha * temperature,
+                Cooling::Linear => t0 * (1.0 - self.budget.progress(level, started)),
+                Cooling::Adaptive {
+                    patience, reheat, ..
+                } if stale >= patience => {
+                    stale = 0;
+                    reheat * t0
+                }
+                Cooling::Adaptive { alpha, .. } => alpha * temperature,
+            };
+        }
+
+        // Recalcula o custo para não acumular os erros de arredondamento das variações.
+        best.cost = Solution::calculate_cost(&best.route, graph);
+        best
+    }
+}
@@ -0,0 +1,41 @@
+use graphs_algorithms::{aco::Aco, budget::Budget, instance, local_search::vnd::Neighbourhood};
+use std::time::Instant;
+
+fn main() {
+    let now = Instant::now();
+    let mut rng = rand::rng();
+
+    // Load instance and hyper-params.
+    let args = std::env::args().collect::<Vec<_>>();
+    let path = &args[1]; // Instance file.
+    let itnum: usize = args[2].parse().unwrap(); // Number of iterations.
+    let ants: usize = args[3].parse().unwrap(); // Number of ants per iteration.
+    let alpha: f64 = args[4].parse().unwrap(); // Pheromone weight.
+    let beta: f64 = args[5].parse().unwrap(); // Visibility weight.
+    let rho: f64 = args[6].parse().unwrap(); // Evaporation rate.
+    let variant = args.get(7).map_or("mmas", String::as_str); // `as` or `mmas`.
+    let ops = args
+        .get(8)
+        .map(|ops| Neighbourhood::parse_list(ops).unwrap()); // Local searches on each ant.
+    assert!(ants > 0 && 0.0 < rho && rho <= 1.0);
+
+    let g = instance::load(path).unwrap_or_else(|e| panic!("Failed to load {path}: {e}"));
+    let aco = Aco {
+        variant: variant.parse().unwrap(),
+        ants,
+        alpha,
+        beta,
+        rho,
+        local_search: ops.unwrap_or_default(),
+        budget: Budget::Iterations(itnum),
+    };
+    let best = aco.run(&g, &mut rng);
+
+    // Print best fitness and time taken, and the gap (%) to the instance reference, if any.
+    let cost = best.cost;
+    let time = now.elapsed().as_secs_f64();
+    println!("{cost} {time}");
+    if let Some(gap) = instance::reference_gap(path, cost) {
+        eprintln!("Gap: {gap:.2}%");
+    }
+}
@@ -0,0 +1,67 @@
+use graphs_algorithms::{
+    annealing::{Annealing, Cooling, MoveKind},
+    budget::Budget,
+    heuristics::nearest_neighbour,
+    instance,
+};
+use std::{fmt::Debug, str::FromStr, time::Instant};
+
+/// Parses the next optional positional argument, falling back to `default` if there is none.
+fn next_arg<'a, T>(args: &mut impl Iterator<Item = &'a str>, default: T) -> T
+where
+    T: FromStr,
+    T::Err: Debug,
+{
+    args.next().map_or(Ok(default), str::parse).unwrap()
+}
+
+fn main() {
+    let now = Instant::now();
+    let mut rng = rand::rng();
+
+    // Load instance and hyper-params.
+    let args = std::env::args().collect::<Vec<_>>();
+    let path = &args[1]; // Instance file.
+    let itnum: usize = args[2].parse().unwrap(); // Number of temperature levels.
+    let epoch: usize = args[3].parse().unwrap(); // Moves tried at each temperature level.
+    let p0: f64 = args[4].parse().unwrap(); // Initial acceptance probability of an average worsening move.
+    let cooling = args.get(5).map_or("geometric", String::as_str); // `geometric`, `linear` or `adaptive`.
+    assert!(0.0 < p0 && p0 < 1.0);
+
+    // Only the parameters used by the cooling schedule follow it, as irace leaves out the others.
+    let mut rest = args.iter().skip(6).map(String::as_str);
+    let cooling = match cooling {
+        "geometric" => Cooling::Geometric {
+            alpha: next_arg(&mut rest, 0.95), // Cooling rate.
+        },
+        "linear" => Cooling::Linear,
+        "adaptive" => Cooling::Adaptive {
+            alpha: next_arg(&mut rest, 0.95),
+            patience: next_arg(&mut rest, 10), // Levels without improvement before reheating.
+            reheat: next_arg(&mut rest, 0.5), // Fraction of the initial temperature after reheating.
+        },
+        s => panic!("unknown cooling schedule: {s}"),
+    };
+    let moves = rest.next().unwrap_or("swap,shift,2opt,oropt"); // Move types.
+
+    let g = instance::load(path).unwrap_or_else(|e| panic!("Failed to load {path}: {e}"));
+    let annealing = Annealing {
+        moves: MoveKind::parse_list(moves).unwrap(),
+        cooling,
+        initial_acceptance: p0,
+        epoch,
+        budget: Budget::Iterations(itnum),
+    };
+
+    // Start from the nearest neighbour tour of a random node.
+    let initial = nearest_neighbour(&g, rand::random_range(0..g.len()));
+    let best = annealing.run(&initial, &g, &mut rng);
+
+    // Print best fitness and time taken, and the gap (%) to the instance reference, if any.
+    let cost = best.cost;
+    let time = now.elapsed().as_secs_f64();
+    println!("{cost} {time}");
+    if let Some(gap) = instance::reference_gap(path, cost) {
+        eprintln!("Gap: {gap:.2}%");
+    }
+}
@@ -0,0 +1,70 @@
+use graphs_algorithms::{
+    budget::Budget,
+    exact::{
+        HELD_KARP_MAX_NODES,
+        branch_and_bound::{BranchAndBoundResult, branch_and_bound},
+        held_karp,
+    },
+    heuristics::nearest_insertion,
+    instance,
+    local_search::{
+        Pivot,
+        vnd::{CandidateLists, Neighbourhood, vnd},
+    },
+    multi_start::multi_start,
+};
+use std::time::Instant;
+
+fn main() {
+    let now = Instant::now();
+
+    // Load instance and the node/time limit of branch-and-bound.
+    let args = std::env::args().collect::<Vec<_>>();
+    let path = args.get(1).map_or("data/012/data.csv", String::as_str);
+    let budget: Budget = args.get(2).map_or("60s", String::as_str).parse().unwrap();
+    let g = instance::load(path).unwrap_or_else(|e| panic!("Failed to load {path}: {e}"));
+
+    // Held-Karp while it fits in memory, branch-and-bound seeded by the heuristics otherwise.
+    let result = if g.len() <= HELD_KARP_MAX_NODES {
+        let best = held_karp(&g).unwrap_or_else(|e| panic!("{path}: {e}"));
+        BranchAndBoundResult {
+            lower_bound: best.cost,
+            best,
+            nodes: 0,
+            optimal: true,
+        }
+    } else {
+        let local_search = [
+            Neighbourhood::TwoOpt(Pivot::FirstImprovement),
+            Neighbourhood::OrOpt(3),
+            Neighbourhood::LinKernighan(5),
+        ];
+        let upper = multi_start(&g, 0..g.len(), |g, start| {
+            vnd(
+                &nearest_insertion(g, start),
+                g,
+                &local_search,
+                &CandidateLists::default(),
+            )
+        });
+        branch_and_bound(&g, &upper.best, budget)
+    };
+
+    eprintln!("Route: {:?}", result.best.route);
+    if !result.optimal {
+        eprintln!(
+            "Stopped after {} nodes: lower bound {}, gap {:.2}%",
+            result.nodes,
+            result.lower_bound,
+            100.0 * result.gap()
+        );
+    }
+
+    // Print best cost and time taken, and the gap (%) to the instance reference, if any.
+    let cost = result.best.cost;
+    let time = now.elapsed().as_secs_f64();
+    println!("{cost} {time}");
+    if let Some(gap) = instance::reference_gap(path, cost) {
+        eprintln!("Gap: {gap:.2}%");
+    }
+}
@@ -1,30 +1,29 @@
 #![feature(slice_swap_unchecked)]
 
-use std::{array, time::Instant};
+use std::{env, time::Instant};
 
-use csv_macro::graph_from_csv;
-use rand::{rngs::ThreadRng, seq::SliceRandom};
+use graphs_algorithms::{instance, matrix::Matrix};
+use rand::{Rng, SeedableRng, random, rngs::StdRng, seq::SliceRandom};
 
-graph_from_csv!("data/006/data.csv");
+/// A node of the graph, identified by its index in the adjacency matrix.
+type Node = usize;
 
 /// A metric for representing the quality of a solution to the TSP problem.
 type Fit = f64;
 
 /// A candidate solution for the TSP problem.
-type Individual = [Node; NODE_COUNT];
+type Individual = Vec<Node>;
 
 /// Pool of current candidate solutions.
 type Population = [Individual];
 
 /// Fills a population with random solutions (individuals).
 #[inline]
-fn init(rng: &mut ThreadRng, p: &mut Population) {
-    let mut rit = 0..NODE_COUNT;
-    // SAFETY: Range iterator was declared above with NODE_COUNT.
-    let mut r: [usize; NODE_COUNT] = array::from_fn(|_| unsafe { rit.next().unwrap_unchecked() });
+fn init(rng: &mut StdRng, g: &Matrix, p: &mut Population) {
+    let mut r: Individual = (0..g.len()).collect();
     for i in p {
         r.shuffle(rng);
-        *i = r;
+        i.clone_from(&r);
     }
 }
 
@@ -33,8 +32,8 @@ fn init(rng: &mut ThreadRng, p: &mut Population) {
 /// In this case, the sum of edge costs between adjacent nodes in the individual, including a cycle
 /// back to the beginning.
 #[inline]
-fn fit(i: &Individual) -> Fit {
-    i.windows(2).map(|w| g[w[0]][w[1]]).sum::<Fit>() + g[i[NODE_COUNT - 1]][i[0]]
+fn fit(g: &Matrix, i: &Individual) -> Fit {
+    i.windows(2).map(|w| g[w[0]][w[1]]).sum::<Fit>() + g[i[i.len() - 1]][i[0]]
 }
 
 /// Executes a crossover between the individuals of two equal halves of a population and
@@ -46,13 +45,13 @@ fn fit(i: &Individual) -> Fit {
 /// the crossover+mutation operation, the population is reshuffled to enable the crossover of
 /// different individuals.
 #[inline]
-fn love(rng: &mut ThreadRng, mrate: f64, p: &mut Population) {
+fn love(rng: &mut StdRng, g: &Matrix, mrate: f64, p: &mut Population) {
     let (h1, h2) = p.split_at_mut(p.len() / 2);
     for (p1, p2) in h1.iter_mut().zip(h2) {
-        if let Some(i) = cross(p1, p2)
-            && rand::random_bool(mrate)
+        if let Some(i) = cross(rng, g, p1, p2)
+            && rng.random_bool(mrate)
         {
-            mutate([p1, p2][i]);
+            mutate(rng, [p1, p2][i]);
         }
     }
     p.shuffle(rng);
@@ -62,10 +61,10 @@ fn love(rng: &mut ThreadRng, mrate: f64, p: &mut Population) {
 /// parent with the offspring if the new individual is more fit. Returns the position of the
 /// overwritten parent in the `[p1, p2]` array.
 #[inline]
-fn cross(p1: &mut Individual, p2: &mut Individual) -> Option<usize> {
-    let mut offspring = [0; NODE_COUNT];
-    let mut visited = [false; NODE_COUNT];
-    let mut fst = [&p1, &p2][rand::random_range(0..2)][0];
+fn cross(rng: &mut StdRng, g: &Matrix, p1: &mut Individual, p2: &mut Individual) -> Option<usize> {
+    let mut offspring = vec![0; g.len()];
+    let mut visited = vec![false; g.len()];
+    let mut fst = [&p1, &p2][rng.random_range(0..2)][0];
     offspring[0] = fst;
     visited[fst] = true;
     for n in offspring.iter_mut().skip(1) {
@@ -76,15 +75,15 @@ fn cross(p1: &mut Individual, p2: &mut Individual) -> Option<usize> {
         visited[*n] = true;
     }
     [p1, p2].iter_mut().enumerate().find_map(|(i, p)| {
-        (fit(&offspring) < fit(p)).then(|| {
-            **p = offspring;
+        (fit(g, &offspring) < fit(g, p)).then(|| {
+            p.clone_from(&offspring);
             i
         })
     })
 }
 
 /// Helper function to find the first _legitimate node_ after `fst` in the crossover operation ([`cross`]).
-fn legitimate(fst: usize, visited: &mut [bool; NODE_COUNT], i: &Individual) -> Node {
+fn legitimate(fst: usize, visited: &mut [bool], i: &Individual) -> Node {
     if let Some(n) = i.iter().enumerate().find_map(|(k, n)| {
         if *n == fst {
             i[k + 1..].iter().find(|n| !visited[**n])
@@ -94,15 +93,15 @@ fn legitimate(fst: usize, visited: &mut [bool; NODE_COUNT], i: &Individual) -> N
     }) {
         *n
     } else {
-        (0..NODE_COUNT).find(|n| !visited[*n]).unwrap()
+        (0..i.len()).find(|n| !visited[*n]).unwrap()
     }
 }
 
 /// Executes a swap operation in a random contiguous pair of nodes in the individual.
 #[inline]
-fn mutate(i: &mut Individual) {
-    let pos = rand::random_range(0..NODE_COUNT - 1);
-    // SAFETY: pos is at least NODE_COUNT - 1 and NODE_COUNT is greater than 1.
+fn mutate(rng: &mut StdRng, i: &mut Individual) {
+    let pos = rng.random_range(0..i.len() - 1);
+    // SAFETY: pos is at most i.len() - 2 and the individual has more than 1 node.
     unsafe {
         i.swap_unchecked(pos, pos + 1);
     }
@@ -113,31 +112,39 @@ const MAX_PSIZE: usize = 200;
 
 fn main() {
     let now = Instant::now();
-    let mut rng = rand::rng();
 
-    // Load hyper-params.
-    let args = std::env::args().collect::<Vec<_>>();
-    let itnum: usize = args[1].parse().unwrap(); // Number of iterations (or generations).
-    let psize: usize = args[2].parse().unwrap(); // Size of the population by number of individuals.
-    let mrate: f64 = args[3].parse().unwrap(); // Mutation rate.
+    // Load instance and hyper-params.
+    let args = env::args().collect::<Vec<_>>();
+    let path = &args[1]; // Instance file.
+    let itnum: usize = args[2].parse().unwrap(); // Number of iterations (or generations).
+    let psize: usize = args[3].parse().unwrap(); // Size of the population by number of individuals.
+    let mrate: f64 = args[4].parse().unwrap(); // Mutation rate.
+    let seed: u64 = env::var("SEED").map_or_else(|_| random(), |s| s.parse().unwrap()); // RNG seed from `$SEED`, random by default.
     assert!(psize <= MAX_PSIZE);
 
+    let g = instance::load(path).unwrap_or_else(|e| panic!("Failed to load {path}: {e}"));
+    let mut rng = StdRng::seed_from_u64(seed);
+
     // Init population.
-    let p: &mut Population = &mut [[0; NODE_COUNT]; MAX_PSIZE][..psize];
-    init(&mut rng, p);
+    let p: &mut Population = &mut vec![Vec::new(); psize];
+    init(&mut rng, &g, p);
 
     // Make love.
     for _ in 0..itnum {
-        love(&mut rng, mrate, p);
+        love(&mut rng, &g, mrate, p);
     }
 
-    // Print best fitness and time taken.
-    println!(
-        "{} {}",
-        p.iter()
-            .map(fit)
-            .min_by(|x, y| x.total_cmp(y))
-            .unwrap_or(f64::INFINITY),
-        now.elapsed().as_secs_f64()
-    )
+    // Print best fitness and time taken, and the seed that reproduces the run and the gap (%) to the
+    // instance reference, if any.
+    let cost = p
+        .iter()
+        .map(|i| fit(&g, i))
+        .min_by(|x, y| x.total_cmp(y))
+        .unwrap_or(f64::INFINITY);
+    let time = now.elapsed().as_secs_f64();
+    println!("{cost} {time}");
+    eprintln!("Seed: {seed}");
+    if let Some(gap) = instance::reference_gap(path, cost) {
+        eprintln!("Gap: {gap:.2}%");
+    }
 }
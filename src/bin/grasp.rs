@@ -0,0 +1,34 @@
+use graphs_algorithms::{budget::Budget, grasp::Grasp, instance, local_search::vnd::Neighbourhood};
+use std::time::Instant;
+
+fn main() {
+    let now = Instant::now();
+    let mut rng = rand::rng();
+
+    // Load instance and hyper-params.
+    let args = std::env::args().collect::<Vec<_>>();
+    let path = &args[1]; // Instance file.
+    let budget: Budget = args[2].parse().unwrap(); // Iterations (e.g. `500`) or seconds (e.g. `2.5s`).
+    let alpha: f64 = args[3].parse().unwrap(); // Size of the restricted candidate list, in [0, 1].
+    let elite_size: usize = args.get(4).map_or(Ok(0), |s| s.parse()).unwrap(); // Path-relinking pool, 0 disables it.
+    let construction = args.get(5).map_or("insertion", String::as_str); // `nn` or `insertion`.
+    let ops = args.get(6).map_or("2opt,oropt", String::as_str); // Local searches.
+    assert!((0.0..=1.0).contains(&alpha));
+
+    let g = instance::load(path).unwrap_or_else(|e| panic!("Failed to load {path}: {e}"));
+    let grasp = Grasp {
+        construction: construction.parse().unwrap(),
+        alpha,
+        local_search: Neighbourhood::parse_list(ops).unwrap(),
+        budget,
+        elite_size,
+    };
+
+    // Print best cost and time taken, and the gap (%) to the instance reference, if any.
+    let cost = grasp.run(&g, &mut rng).cost;
+    let time = now.elapsed().as_secs_f64();
+    println!("{cost} {time}");
+    if let Some(gap) = instance::reference_gap(path, cost) {
+        eprintln!("Gap: {gap:.2}%");
+    }
+}
@@ -1,194 +1,129 @@
-use csv_macro::graph_from_csv;
-use graphs_algorithms::local_search::{LocalSearch, Solution};
+use graphs_algorithms::{
+    graphs::Graph,
+    heuristics::{
+        arbitrary_insertion, cheapest_insertion, clarke_wright, farthest_insertion, greedy_edge,
+        nearest_insertion, nearest_neighbour, random_insertion,
+    },
+    instance,
+    local_search::{
+        Solution,
+        vnd::{CandidateLists, Neighbourhood, vnd},
+    },
+    mst::{christofides, double_tree, triangle_inequality_violation},
+    multi_start::{multi_start, sample_starts},
+};
+
+/// Heurística construtiva, que recebe o grafo e o vértice inicial.
+type Constructor = fn(&Graph, usize) -> Solution;
 
-graph_from_csv!("data/012/data.csv");
-
-/// Implementação da heurística do Vizinho Mais Próximo (Nearest Neighbor), um algoritmo
-/// guloso que gera um caminho para o Problema do Caixeiro Viajante.
-///
-/// # Argumentos
-/// - `graph`: grafo representado em matriz de adjacência;
-/// - `start`: nó inicial por onde o caminho começará.
-///
-/// # Saída
-/// Retorna uma `Solution`, com a rota encontrada e o custo dela.
-///
-/// # Detalhes de funcionamento
-/// 1. Cria um vetor booleano `visited`, onde é possível verificar em O(1) se um
-///    vértice já foi visitado. Todos começam como "não visitados".
-/// 2. Marca o vértice inicial como visitado.
-/// 3. Cria um contador com a quantidade de vértices visitados. Isso permite
-///    determinar o fim do loop sem precisar usar uma checagem O(n).
-/// 4. Enquanto houver vértices não visitados, itera sobre eles na matriz em busca
-///    da aresta de menor custo. Ao encontrá-la, adiciona seu vértice adjacente ao
-///    caminho, marca-o como visitado e incrementa o contador.
-/// 5. Ao fim do loop, adiciona ao custo a aresta que liga o último vértice do
-///    caminho ao vértice inicial, sem adicionar este novamente ao caminho.
-/// 6. Retorna o caminho encontrado e o custo total.
-///
-/// Observação: este algoritmo **não** insere o vértice inicial no fim do caminho,
-/// pois assume-se que há um ciclo hamiltoniano implícito; portanto, adicionar o
-/// vértice inicial novamente não é necessário.
-fn nearest_neighbour(graph: &Graph, start: usize) -> Solution<NODE_COUNT> {
-    let mut visited: Vec<bool> = vec![false; graph.len()];
-    let mut path: Vec<usize> = Vec::new();
-    path.push(start);
-
-    let mut cost: f64 = 0.0;
-    let mut current_node = start;
-    visited[current_node] = true;
-
-    let mut visited_count = 1;
-
-    let mut better_cost;
-    let mut next_on_path: Option<usize> = None;
-
-    while visited_count != graph.len() {
-        better_cost = f64::INFINITY;
-
-        for (i, val) in visited.iter().enumerate() {
-            if *val || current_node == i {
-                continue;
-            }
-
-            if graph[current_node][i] < better_cost {
-                better_cost = graph[current_node][i];
-                next_on_path = Some(i);
-            }
-        }
-
-        if let Some(next_on_path) = next_on_path {
-            let n = next_on_path;
-            path.push(n);
-            cost += better_cost;
-            current_node = n;
-            visited[n] = true;
-            visited_count += 1;
-        }
+fn main() {
+    // Load instance.
+    let args = std::env::args().collect::<Vec<_>>();
+    let path = args.get(1).map_or("data/012/data.csv", String::as_str);
+    let g = instance::load(path).unwrap_or_else(|e| panic!("Failed to load {path}: {e}"));
+
+    // The MST-based tours only keep their approximation guarantees on metric instances.
+    if let Some((i, j, k)) = triangle_inequality_violation(&g) {
+        eprintln!(
+            "warning: d({i}, {j}) > d({i}, {k}) + d({k}, {j}), the double tree and Christofides \
+             guarantees do not hold for {path}"
+        );
     }
 
-    cost += graph[current_node][start];
-
-    Solution { route: path, cost }
-}
-
-/// Implementação da Heurística de Inserção Mais Próxima (*Nearest Insertion*), um algoritmo
-/// guloso utilizado para gerar um ciclo aproximado para o Problema do Caixeiro Viajante (TSP).
-///
-/// # Argumentos
-/// - `graph`: um grafo representado por uma matriz de adjacência, onde `graph[i][j]` indica
-///   o custo (ou distância) da aresta entre os vértices `i` e `j`;
-/// - `start`: o vértice inicial que será utilizado como base para a construção do ciclo.
-///
-/// # Saída
-/// - Um vetor de vértices que representa o ciclo encontrado, iniciando e terminando no vértice `start`.
-///
-/// # Detalhes de funcionamento
-/// 1. Cria-se um vetor de booleanos `in_cycle` para marcar se um vértice já pertence ao ciclo,
-///    permitindo consultas em tempo O(1).
-/// 2. Inicializa o ciclo com o vértice `start` e o seu vizinho mais próximo, formando um ciclo
-///    inicial mínimo do tipo `start → v → start`.
-/// 3. Cria-se um vetor auxiliar `min_dist`, que armazena, para cada vértice fora do ciclo,
-///    a menor distância até qualquer vértice já presente no ciclo.
-/// 4. Enquanto ainda houver vértices fora do ciclo, escolhe-se o vértice `r*` cuja distância
-///    ao ciclo é mínima (critério de *nearest*).
-/// 5. Para o vértice escolhido, testa-se todas as arestas consecutivas do ciclo a fim de encontrar
-///    a posição de inserção que minimize o custo adicional:
-///    custo_extra = d(u, r*) + d(r*, v) − d(u, v).
-/// 6. O vértice `r*` é então inserido na melhor posição do ciclo e marcado como pertencente ao ciclo.
-/// 7. O vetor `min_dist` é atualizado em tempo O(n), ajustando as distâncias mínimas dos vértices
-///    ainda não inseridos.
-/// 8. O processo continua até que todos os vértices estejam presentes no ciclo.
-fn nearest_insertion(graph: &Graph, start: usize) -> Solution<NODE_COUNT> {
-    let n = graph.len();
-    let mut in_cycle = vec![false; n];
-    let mut min_dist = vec![f64::INFINITY; n];
-    in_cycle[start] = true;
-
-    let mut first: Option<usize> = None;
-    let mut best = f64::INFINITY;
-
-    for (i, row) in graph.iter().enumerate().take(n) {
-        if i != start && row[start] < best {
-            best = row[start];
-            first = Some(i);
-        }
+    // Gap (%) to the instance reference value, if any.
+    let gap = |cost: f64| {
+        instance::reference_gap(path, cost).map_or(String::new(), |gap| format!("(gap {gap:.2}%) "))
+    };
+
+    // Constructive heuristics on their own.
+    let constructors: [(&str, Constructor); 10] = [
+        ("nearest neighbour", nearest_neighbour),
+        ("nearest insertion", nearest_insertion),
+        ("farthest insertion", farthest_insertion),
+        ("cheapest insertion", cheapest_insertion),
+        ("random insertion", |g, s| {
+            random_insertion(g, s, &mut rand::rng())
+        }),
+        ("arbitrary insertion", arbitrary_insertion),
+        ("greedy edge", |g, _| greedy_edge(g)),
+        ("clarke-wright savings", clarke_wright),
+        ("double tree", double_tree),
+        ("christofides", christofides),
+    ];
+    for (name, construct) in constructors {
+        print!("- Using {name} heuristic... ");
+        let cost = construct(&g, 0).cost;
+        println!("Cost: {cost} {}", gap(cost));
     }
 
-    let first = first.expect("Invalid graph: no vertex found to start the cycle");
-    in_cycle[first] = true;
-    let mut cycle = vec![start, first, start];
-    for v in 0..n {
-        if !in_cycle[v] {
-            min_dist[v] = graph[v][start].min(graph[v][first]);
-        }
+    // Constructive heuristics and the local searches (applied through VND) to compare.
+    let mut pipelines: Vec<(&str, Constructor, Vec<Neighbourhood>)> = vec![
+        (
+            "nearest neighbour",
+            nearest_neighbour,
+            vec![Neighbourhood::Swap],
+        ),
+        (
+            "nearest insertion",
+            nearest_insertion,
+            vec![Neighbourhood::OrOpt(3)],
+        ),
+        (
+            "nearest insertion",
+            nearest_insertion,
+            vec![Neighbourhood::ThreeOpt],
+        ),
+        (
+            "nearest insertion",
+            nearest_insertion,
+            vec![Neighbourhood::Or3Opt],
+        ),
+        (
+            "nearest insertion",
+            nearest_insertion,
+            vec![Neighbourhood::LinKernighan(5)],
+        ),
+    ];
+    if let Some(custom) = args.get(2) {
+        let neighbourhoods = Neighbourhood::parse_list(custom).unwrap_or_else(|e| panic!("{e}"));
+        pipelines.push((
+            "nearest neighbour",
+            nearest_neighbour,
+            neighbourhoods.clone(),
+        ));
+        pipelines.push(("nearest insertion", nearest_insertion, neighbourhoods));
     }
 
-    let mut count_in_cycle = 2;
-    while count_in_cycle < n {
-        let mut r_star: Option<usize> = None;
-        let mut best_dist = f64::INFINITY;
-
-        for v in 0..n {
-            if !in_cycle[v] && min_dist[v] < best_dist {
-                best_dist = min_dist[v];
-                r_star = Some(v);
-            }
-        }
-
-        let r_star = r_star.expect("No candidate vertex found");
-        let mut best_extra = f64::INFINITY;
-        let mut best_pos = 0;
-
-        for i in 0..cycle.len() - 1 {
-            let u = cycle[i];
-            let v = cycle[i + 1];
-
-            let du = graph[u][r_star];
-            let dv = graph[r_star][v];
-            let uv = graph[u][v];
-
-            let extra = du + dv - uv;
-
-            if extra < best_extra {
-                best_extra = extra;
-                best_pos = i;
-            }
-        }
-
-        cycle.insert(best_pos + 1, r_star);
-        in_cycle[r_star] = true;
-        count_in_cycle += 1;
-
-        for v in 0..n {
-            if !in_cycle[v] {
-                let d = graph[v][r_star];
-                if d < min_dist[v] {
-                    min_dist[v] = d;
-                }
-            }
+    // Every pipeline runs from every start node, or from a sample of them if a size is given.
+    let starts = match args.get(3) {
+        Some(count) => {
+            let count = count.parse().expect("Invalid number of start nodes");
+            sample_starts(g.len(), count, &mut rand::rng())
         }
+        None => (0..g.len()).collect(),
+    };
+
+    for (name, construct, neighbourhoods) in pipelines {
+        let local_search = neighbourhoods
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        print!(
+            "- Using {name} heuristic with {} as local search from {} start nodes... ",
+            local_search.join(" + "),
+            starts.len()
+        );
+        let candidates = CandidateLists::new(&g, &neighbourhoods);
+        let result = multi_start(&g, starts.iter().copied(), |g, start| {
+            vnd(&construct(g, start), g, &neighbourhoods, &candidates)
+        });
+        println!(
+            "Best: {} {}(start {}), mean: {}, worst: {} ",
+            result.best.cost,
+            gap(result.best.cost),
+            result.best_start,
+            result.mean,
+            result.worst
+        );
     }
-
-    cycle.pop();
-    let cost = Solution::calculate_cost(&cycle, graph);
-
-    Solution { route: cycle, cost }
-}
-
-fn nearest_neighbour_with_swap(graph: &Graph, start: usize) -> Solution<NODE_COUNT> {
-    let first_solution = nearest_neighbour(graph, start);
-    first_solution.swap(graph, start)
-}
-
-fn nearest_insertion_with_or_opt(graph: &Graph, start: usize) -> Solution<NODE_COUNT> {
-    let first_solution = nearest_insertion(graph, start);
-    first_solution.or_opt(graph)
-}
-
-fn main() {
-    print!("- Using nearest neighbour heuristic with swap as local search... ");
-    println!("Cost: {} ", nearest_neighbour_with_swap(&g, 0).cost);
-    print!("- Using nearest insertion heuristic with or-opt as local search... ");
-    println!("Cost: {} ", nearest_insertion_with_or_opt(&g, 0).cost);
 }
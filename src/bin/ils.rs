@@ -0,0 +1,41 @@
+use graphs_algorithms::{
+    budget::Budget,
+    heuristics::nearest_neighbour,
+    ils::Ils,
+    instance,
+    local_search::{Solution, vnd::Neighbourhood},
+};
+use std::time::Instant;
+
+fn main() {
+    let now = Instant::now();
+    let mut rng = rand::rng();
+
+    // Load instance and hyper-params.
+    let args = std::env::args().collect::<Vec<_>>();
+    let path = &args[1]; // Instance file.
+    let itnum: usize = args[2].parse().unwrap(); // Number of iterations (or kicks).
+    let acceptance = args.get(3).map_or("better", String::as_str); // `better`, `walk` or `restart:K`.
+    let perturbation = args.get(4).map_or("double-bridge", String::as_str); // Or `reversal`.
+    let ops = args.get(5).map_or("2opt,oropt", String::as_str); // Local searches.
+
+    let g = instance::load(path).unwrap_or_else(|e| panic!("Failed to load {path}: {e}"));
+    let ils = Ils {
+        perturbation: perturbation.parse().unwrap(),
+        acceptance: acceptance.parse().unwrap(),
+        local_search: Neighbourhood::parse_list(ops).unwrap(),
+        budget: Budget::Iterations(itnum),
+    };
+
+    // Start from the nearest neighbour tour of a random node.
+    let initial: Solution = nearest_neighbour(&g, rand::random_range(0..g.len()));
+    let best = ils.run(&initial, &g, &mut rng);
+
+    // Print best fitness and time taken, and the gap (%) to the instance reference, if any.
+    let cost = best.cost;
+    let time = now.elapsed().as_secs_f64();
+    println!("{cost} {time}");
+    if let Some(gap) = instance::reference_gap(path, cost) {
+        eprintln!("Gap: {gap:.2}%");
+    }
+}
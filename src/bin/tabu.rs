@@ -0,0 +1,51 @@
+use graphs_algorithms::{
+    budget::Budget,
+    heuristics::nearest_neighbour,
+    instance,
+    tabu::{MoveKind, Tabu},
+};
+use std::{fs, time::Instant};
+
+fn main() {
+    let now = Instant::now();
+
+    // Load instance and hyper-params.
+    let args = std::env::args().collect::<Vec<_>>();
+    let path = &args[1]; // Instance file.
+    let itnum: usize = args[2].parse().unwrap(); // Number of iterations.
+    let tenure: usize = args[3].parse().unwrap(); // Iterations a removed edge stays tabu.
+    let fweight: f64 = args.get(4).map_or(Ok(0.0), |s| s.parse()).unwrap(); // Frequency memory weight, 0 disables it.
+    let moves = args.get(5).map_or("2opt,swap", String::as_str); // Neighbourhoods.
+    let history = args.get(6); // Optional file for the best cost of each iteration.
+
+    let g = instance::load(path).unwrap_or_else(|e| panic!("Failed to load {path}: {e}"));
+    let tabu = Tabu {
+        moves: MoveKind::parse_list(moves).unwrap(),
+        tenure,
+        frequency_weight: fweight,
+        budget: Budget::Iterations(itnum),
+    };
+
+    // Start from the nearest neighbour tour of a random node.
+    let initial = nearest_neighbour(&g, rand::random_range(0..g.len()));
+    let result = tabu.run(&initial, &g);
+
+    // Save the best cost per iteration.
+    if let Some(file) = history {
+        let lines = result
+            .history
+            .iter()
+            .enumerate()
+            .map(|(i, cost)| format!("{} {cost}\n", i + 1))
+            .collect::<String>();
+        fs::write(file, lines).unwrap_or_else(|e| panic!("Failed to write {file}: {e}"));
+    }
+
+    // Print best fitness and time taken, and the gap (%) to the instance reference, if any.
+    let cost = result.best.cost;
+    let time = now.elapsed().as_secs_f64();
+    println!("{cost} {time}");
+    if let Some(gap) = instance::reference_gap(path, cost) {
+        eprintln!("Gap: {gap:.2}%");
+    }
+}
@@ -0,0 +1,65 @@
+//! Critério de parada das metaheurísticas.
+
+use std::{
+    fmt,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+/// Orçamento de uma metaheurística: uma quantidade de iterações ou um tempo máximo.
+///
+/// Pode ser lido a partir de um número de iterações (e.g. `500`) ou de um tempo em segundos
+/// seguido de `s` (e.g. `2.5s`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Budget {
+    Iterations(usize),
+    Time(Duration),
+}
+
+impl Budget {
+    /// Verifica se o orçamento acabou após `iteration` iterações, iniciadas em `started`.
+    pub fn exhausted(&self, iteration: usize, started: Instant) -> bool {
+        match *self {
+            Budget::Iterations(max) => iteration >= max,
+            Budget::Time(max) => started.elapsed() >= max,
+        }
+    }
+
+    /// Fração do orçamento já consumida, entre 0 e 1.
+    pub fn progress(&self, iteration: usize, started: Instant) -> f64 {
+        let progress = match *self {
+            Budget::Iterations(max) => iteration as f64 / max as f64,
+            Budget::Time(max) => started.elapsed().as_secs_f64() / max.as_secs_f64(),
+        };
+        progress.clamp(0.0, 1.0)
+    }
+}
+
+impl FromStr for Budget {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        match s.strip_suffix('s') {
+            Some(secs) => secs
+                .parse()
+                .ok()
+                .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+                .map(Budget::Time)
+                .ok_or_else(|| format!("invalid time budget: {s}")),
+            None => s
+                .parse()
+                .map(Budget::Iterations)
+                .map_err(|_| format!("invalid iteration budget: {s}")),
+        }
+    }
+}
+
+impl fmt::Display for Budget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Budget::Iterations(max) => write!(f, "{max} iterations"),
+            Budget::Time(max) => write!(f, "{}s", max.as_secs_f64()),
+        }
+    }
+}
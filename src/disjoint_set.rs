@@ -0,0 +1,44 @@
+/// Conjuntos disjuntos (*union-find*) com compressão de caminho e união por tamanho.
+pub(crate) struct DisjointSet {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+}
+
+impl DisjointSet {
+    pub(crate) fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+        }
+    }
+
+    /// Representante do conjunto de `v`.
+    pub(crate) fn find(&mut self, v: usize) -> usize {
+        let mut root = v;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+
+        let mut v = v;
+        while self.parent[v] != root {
+            v = std::mem::replace(&mut self.parent[v], root);
+        }
+
+        root
+    }
+
+    /// Une os conjuntos de `u` e `v`. Retorna `false` se já estavam no mesmo conjunto.
+    pub(crate) fn union(&mut self, u: usize, v: usize) -> bool {
+        let (mut u, mut v) = (self.find(u), self.find(v));
+        if u == v {
+            return false;
+        }
+
+        if self.size[u] < self.size[v] {
+            std::mem::swap(&mut u, &mut v);
+        }
+        self.parent[v] = u;
+        self.size[u] += self.size[v];
+        true
+    }
+}
@@ -0,0 +1,180 @@
+//! Algoritmos exatos para o Problema do Caixeiro Viajante, que retornam a rota ótima: Held–Karp
+//! para instâncias pequenas e [`branch_and_bound`] para as demais.
+
+pub mod branch_and_bound;
+
+use std::{error, fmt};
+
+use crate::{local_search::Solution, matrix::DistanceMatrix};
+
+/// Maior quantidade de vértices aceita por [`held_karp`]. Com 20 vértices, as tabelas ocupam
+/// cerca de 90 MiB.
+pub const HELD_KARP_MAX_NODES: usize = 20;
+
+/// Erro de um algoritmo exato.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExactError {
+    /// A instância tem mais vértices do que o algoritmo comporta no orçamento de memória.
+    TooLarge {
+        nodes: usize,
+        max_nodes: usize,
+        /// Memória, em bytes, que seria necessária.
+        required: u128,
+    },
+}
+
+impl fmt::Display for ExactError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExactError::TooLarge {
+                nodes,
+                max_nodes,
+                required,
+            } => write!(
+                f,
+                "instance has {nodes} nodes, but at most {max_nodes} are supported \
+                 ({:.1} MiB would be required)",
+                *required as f64 / (1 << 20) as f64
+            ),
+        }
+    }
+}
+
+impl error::Error for ExactError {}
+
+/// Memória, em bytes, usada pelas tabelas de [`held_karp`] para `n` vértices.
+fn held_karp_memory(n: usize) -> u128 {
+    let m = n.saturating_sub(1) as u32;
+    (1u128 << m) * m as u128 * (size_of::<f64>() + size_of::<u8>()) as u128
+}
+
+/// Algoritmo de Held–Karp: programação dinâmica sobre subconjuntos, em O(2^n · n²) de tempo e
+/// O(2^n · n) de memória.
+///
+/// # Argumentos
+/// - `graph`: grafo representado em matriz de adjacência, simétrica ou não.
+///
+/// # Saída
+/// A rota ótima, começando no vértice `0`, ou [`ExactError::TooLarge`] caso a instância tenha
+/// mais de [`HELD_KARP_MAX_NODES`] vértices.
+///
+/// # Detalhes de funcionamento
+/// 1. Fixa o vértice `0` como início. `cost[S][j]` é o custo do menor caminho que sai de `0`,
+///    visita exatamente os vértices do conjunto `S` (sem o `0`) e termina em `j ∈ S`;
+/// 2. Para `S = {j}`, `cost[S][j] = d(0, j)`;
+/// 3. Para conjuntos maiores, em ordem crescente de máscara de bits,
+///    `cost[S][j] = min_{i ∈ S − {j}} cost[S − {j}][i] + d(i, j)`, guardando o `i` escolhido;
+/// 4. O custo ótimo é `min_j cost[V − {0}][j] + d(j, 0)`, e a rota é reconstruída de trás para
+///    frente pelos predecessores guardados.
+pub fn held_karp<M: DistanceMatrix + ?Sized>(graph: &M) -> Result<Solution, ExactError> {
+    let n = graph.node_count();
+    if n > HELD_KARP_MAX_NODES {
+        return Err(ExactError::TooLarge {
+            nodes: n,
+            max_nodes: HELD_KARP_MAX_NODES,
+            required: held_karp_memory(n),
+        });
+    }
+    if n <= 2 {
+        let route: Vec<usize> = (0..n).collect();
+        let cost = Solution::calculate_cost(&route, graph);
+        return Ok(Solution { route, cost });
+    }
+
+    // Os vértices 1..n são representados pelos bits 0..m.
+    let m = n - 1;
+    let full = (1usize << m) - 1;
+    let mut cost = vec![f64::INFINITY; (full + 1) * m];
+    let mut parent = vec![u8::MAX; (full + 1) * m];
+    for j in 0..m {
+        cost[(1 << j) * m + j] = graph.cost(0, j + 1);
+    }
+
+    for set in 1..=full {
+        for j in (0..m).filter(|&j| set & 1 << j != 0) {
+            let rest = set ^ 1 << j;
+            if rest == 0 {
+                continue;
+            }
+
+            for i in (0..m).filter(|&i| rest & 1 << i != 0) {
+                let candidate = cost[rest * m + i] + graph.cost(i + 1, j + 1);
+                if candidate < cost[set * m + j] {
+                    cost[set * m + j] = candidate;
+                    parent[set * m + j] = i as u8;
+                }
+            }
+        }
+    }
+
+    let (mut last, best) = (0..m)
+        .map(|j| (j, cost[full * m + j] + graph.cost(j + 1, 0)))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .expect("The instance has at least three nodes");
+
+    let mut route = Vec::with_capacity(n);
+    let mut set = full;
+    while set != 0 {
+        route.push(last + 1);
+        let previous = parent[set * m + last];
+        set ^= 1 << last;
+        last = previous as usize;
+    }
+    route.push(0);
+    route.reverse();
+
+    Ok(Solution { route, cost: best })
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+
+    use super::*;
+    use crate::matrix::Matrix;
+
+    /// Custo da melhor rota que começa em `0`, por força bruta sobre as permutações de `rest`.
+    fn brute_force(graph: &Matrix, route: &mut Vec<usize>, rest: &mut Vec<usize>) -> f64 {
+        if rest.is_empty() {
+            return Solution::calculate_cost(route, graph);
+        }
+        let mut best = f64::INFINITY;
+        for i in 0..rest.len() {
+            route.push(rest.remove(i));
+            best = best.min(brute_force(graph, route, rest));
+            rest.insert(i, route.pop().unwrap());
+        }
+        best
+    }
+
+    #[test]
+    fn held_karp_matches_brute_force_on_asymmetric_matrices() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for n in 1..=7 {
+            for _ in 0..20 {
+                let mut graph = Matrix::new(n);
+                for u in 0..n {
+                    for v in (0..n).filter(|&v| v != u) {
+                        graph[u][v] = rng.random_range(1.0..100.0);
+                    }
+                }
+
+                let solution = held_karp(&graph).unwrap();
+                let mut sorted = solution.route.clone();
+                sorted.sort_unstable();
+                assert_eq!(sorted, (0..n).collect::<Vec<_>>());
+                assert!(
+                    (solution.cost - Solution::calculate_cost(&solution.route, &graph)).abs()
+                        < 1e-9
+                );
+
+                let expected = brute_force(&graph, &mut vec![0], &mut (1..n).collect());
+                assert!(
+                    (solution.cost - expected).abs() < 1e-9,
+                    "n = {n}: cost {}, expected {expected}",
+                    solution.cost
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,248 @@
+//! *Branch-and-bound* em profundidade para o Problema do Caixeiro Viajante.
+//!
+//! Cada nó da árvore de busca é um caminho parcial que começa no vértice `0`; os filhos
+//! estendem o caminho com um vértice ainda não visitado. Um nó é podado quando o seu limite
+//! inferior não é menor que o custo da melhor rota conhecida.
+
+use std::time::Instant;
+
+use crate::{
+    budget::Budget,
+    local_search::Solution,
+    lower_bound::{Penalized, gap, held_karp_bound},
+    matrix::DistanceMatrix,
+};
+
+/// Resultado do *branch-and-bound*.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BranchAndBoundResult {
+    /// Melhor rota encontrada.
+    pub best: Solution,
+    /// Limite inferior provado para o custo ótimo. Igual ao custo de `best` caso a busca tenha
+    /// terminado.
+    pub lower_bound: f64,
+    /// Quantidade de nós explorados.
+    pub nodes: usize,
+    /// Se a busca terminou antes do fim do orçamento, i.e., se `best` é ótima.
+    pub optimal: bool,
+}
+
+impl BranchAndBoundResult {
+    /// Distância relativa provada entre a melhor rota e o ótimo: `(custo − limite) / custo`.
+    pub fn gap(&self) -> f64 {
+        gap(self.best.cost, self.lower_bound)
+    }
+}
+
+/// Limite inferior do 1-tree para completar um caminho parcial que termina em `last`: o caminho
+/// que falta sai de `last`, visita todos os vértices de `rest` e volta ao `0`, logo custa ao
+/// menos uma árvore geradora mínima de `rest` mais a aresta mais barata de `last` até `rest` e a
+/// aresta mais barata de `rest` até o `0`.
+///
+/// Em matrizes assimétricas, a árvore usa o menor custo entre os dois sentidos de cada aresta.
+fn one_tree_bound<M: DistanceMatrix + ?Sized>(graph: &M, last: usize, rest: &[usize]) -> f64 {
+    let Some(&first) = rest.first() else {
+        return graph.cost(last, 0);
+    };
+    let d = |u: usize, v: usize| graph.cost(u, v).min(graph.cost(v, u));
+
+    // Prim em O(k²) sobre `rest`.
+    let mut best: Vec<f64> = rest.iter().map(|&v| d(first, v)).collect();
+    let mut in_tree = vec![false; rest.len()];
+    in_tree[0] = true;
+    let mut tree = 0.0;
+    for _ in 1..rest.len() {
+        let (k, _) = best
+            .iter()
+            .enumerate()
+            .filter(|&(k, _)| !in_tree[k])
+            .min_by(|a, b| a.1.total_cmp(b.1))
+            .expect("No vertex left outside the tree");
+        in_tree[k] = true;
+        tree += best[k];
+        for (l, &v) in rest.iter().enumerate() {
+            if !in_tree[l] {
+                best[l] = best[l].min(d(rest[k], v));
+            }
+        }
+    }
+
+    let leave = rest
+        .iter()
+        .map(|&v| graph.cost(last, v))
+        .fold(f64::INFINITY, f64::min);
+    let enter = rest
+        .iter()
+        .map(|&v| graph.cost(v, 0))
+        .fold(f64::INFINITY, f64::min);
+    tree + leave + enter
+}
+
+/// Limite inferior da matriz de custos reduzida: cada vértice de `{last} ∪ rest` ainda precisa
+/// de uma aresta de saída e cada vértice de `rest ∪ {0}` de uma de entrada. Subtrair o mínimo de
+/// cada linha e, em seguida, de cada coluna dessa submatriz dá um limite inferior para a
+/// designação (e, portanto, para o caminho que falta).
+fn reduced_cost_bound<M: DistanceMatrix + ?Sized>(graph: &M, last: usize, rest: &[usize]) -> f64 {
+    if rest.is_empty() {
+        return graph.cost(last, 0);
+    }
+
+    let rows: Vec<usize> = std::iter::once(last).chain(rest.iter().copied()).collect();
+    let cols: Vec<usize> = rest.iter().copied().chain(std::iter::once(0)).collect();
+    // A aresta `last → 0` fecharia o ciclo antes de visitar `rest`.
+    let allowed = |u: usize, v: usize| u != v && !(u == last && v == 0);
+
+    let row_min: Vec<f64> = rows
+        .iter()
+        .map(|&u| {
+            cols.iter()
+                .filter(|&&v| allowed(u, v))
+                .map(|&v| graph.cost(u, v))
+                .fold(f64::INFINITY, f64::min)
+        })
+        .collect();
+    let col_min = cols.iter().map(|&v| {
+        rows.iter()
+            .zip(&row_min)
+            .filter(|&(&u, _)| allowed(u, v))
+            .map(|(&u, &r)| graph.cost(u, v) - r)
+            .fold(f64::INFINITY, f64::min)
+    });
+
+    row_min.iter().sum::<f64>() + col_min.sum::<f64>()
+}
+
+/// Estado da busca em profundidade.
+struct Search<'a, M: DistanceMatrix + ?Sized> {
+    graph: &'a M,
+    budget: Budget,
+    started: Instant,
+    path: Vec<usize>,
+    visited: Vec<bool>,
+    best: Solution,
+    nodes: usize,
+    /// Menor limite inferior dentre os nós abandonados pelo fim do orçamento.
+    abandoned: f64,
+}
+
+impl<M: DistanceMatrix + ?Sized> Search<'_, M> {
+    /// Limite inferior de um caminho parcial de custo `cost`, que termina em `last` e ainda
+    /// precisa visitar `rest`.
+    fn bound(&self, last: usize, cost: f64, rest: &[usize]) -> f64 {
+        cost + one_tree_bound(self.graph, last, rest)
+            .max(reduced_cost_bound(self.graph, last, rest))
+    }
+
+    /// Explora o nó do caminho `self.path`, de custo `cost` e limite inferior `bound`.
+    fn explore(&mut self, cost: f64, bound: f64) {
+        if self.budget.exhausted(self.nodes, self.started) {
+            self.abandoned = self.abandoned.min(bound);
+            return;
+        }
+        self.nodes += 1;
+
+        let n = self.visited.len();
+        let last = self.path[self.path.len() - 1];
+        if self.path.len() == n {
+            let total = cost + self.graph.cost(last, 0);
+            if total < self.best.cost {
+                self.best = Solution {
+                    route: self.path.clone(),
+                    cost: total,
+                };
+            }
+            return;
+        }
+
+        // Filhos em ordem crescente de limite inferior.
+        let mut children: Vec<(usize, f64, f64)> = (0..n)
+            .filter(|&v| !self.visited[v])
+            .map(|v| {
+                let cost = cost + self.graph.cost(last, v);
+                let rest: Vec<usize> = (0..n).filter(|&u| !self.visited[u] && u != v).collect();
+                (v, cost, self.bound(v, cost, &rest))
+            })
+            .collect();
+        children.sort_by(|a, b| a.2.total_cmp(&b.2));
+
+        for (v, cost, bound) in children {
+            if bound >= self.best.cost {
+                break;
+            }
+            self.visited[v] = true;
+            self.path.push(v);
+            self.explore(cost, bound);
+            self.path.pop();
+            self.visited[v] = false;
+        }
+    }
+}
+
+/// *Branch-and-bound* em profundidade.
+///
+/// # Argumentos
+/// - `graph`: grafo representado em matriz de adjacência;
+/// - `initial`: rota inicial, e.g. de uma heurística, cujo custo é o limite superior inicial;
+/// - `budget`: limite de nós explorados ou de tempo.
+///
+/// # Saída
+/// A melhor rota encontrada, o limite inferior provado e se a busca terminou (i.e., se a rota é
+/// ótima). Caso o orçamento acabe antes, o limite inferior é o menor dentre os limites dos nós
+/// não explorados, e [`BranchAndBoundResult::gap`] dá a distância máxima até o ótimo.
+///
+/// # Detalhes de funcionamento
+/// 1. Calcula as penalidades do limite de Held–Karp ([`held_karp_bound`]) e faz a busca sobre a
+///    matriz penalizada, que tem a mesma rota ótima e na qual o limite do 1-tree da raiz é o
+///    próprio limite de Held–Karp;
+/// 2. A raiz é o caminho `[0]`;
+/// 3. O limite inferior de um nó é o custo do caminho parcial mais o maior entre o limite do
+///    1-tree e o da matriz de custos reduzida para o caminho que falta;
+/// 4. Os filhos são explorados em ordem crescente de limite inferior, e a busca volta assim que
+///    o limite do próximo filho não for menor que o custo da melhor rota conhecida.
+pub fn branch_and_bound<M: DistanceMatrix + ?Sized>(
+    graph: &M,
+    initial: &Solution,
+    budget: Budget,
+) -> BranchAndBoundResult {
+    let n = graph.node_count();
+    let mut visited = vec![false; n];
+    if n > 0 {
+        visited[0] = true;
+    }
+
+    let root_bound = held_karp_bound(graph, initial.cost, 100 * n);
+    let penalized = Penalized {
+        graph,
+        penalties: &root_bound.penalties,
+        symmetrize: false,
+    };
+    let shift = penalized.shift();
+
+    let mut search = Search {
+        graph: &penalized,
+        budget,
+        started: Instant::now(),
+        path: vec![0],
+        visited,
+        best: Solution {
+            route: initial.route.clone(),
+            cost: initial.cost + shift,
+        },
+        nodes: 0,
+        abandoned: f64::INFINITY,
+    };
+    if n > 1 && root_bound.value < initial.cost {
+        let rest: Vec<usize> = (1..n).collect();
+        let root = search.bound(0, 0.0, &rest);
+        search.explore(0.0, root);
+    }
+
+    let route = search.best.route;
+    let cost = Solution::calculate_cost(&route, graph);
+    BranchAndBoundResult {
+        lower_bound: (search.abandoned - shift).max(root_bound.value).min(cost),
+        optimal: search.abandoned == f64::INFINITY,
+        nodes: search.nodes,
+        best: Solution { route, cost },
+    }
+}
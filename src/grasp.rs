@@ -0,0 +1,286 @@
+//! *Greedy Randomized Adaptive Search Procedure* (GRASP) para o Problema do Caixeiro Viajante.
+//!
+//! Cada iteração constrói uma rota com uma heurística gulosa aleatorizada por uma lista restrita
+//! de candidatos (*restricted candidate list*, RCL), melhora-a com uma busca local e, caso haja um
+//! conjunto de soluções elite, aplica *path-relinking* entre a rota e uma delas.
+
+use std::{fmt, str::FromStr, time::Instant};
+
+use rand::{Rng, seq::IndexedRandom};
+
+use crate::{
+    budget::Budget,
+    heuristics::insertion,
+    local_search::{
+        EPSILON, Solution,
+        moves::{Move, Swap},
+        vnd::{CandidateLists, Neighbourhood, vnd},
+    },
+    matrix::DistanceMatrix,
+};
+
+/// Sorteia um candidato da lista restrita de candidatos: dentre os pares `(candidato, custo)`,
+/// são elegíveis aqueles com `custo <= min + alpha * (max - min)`.
+///
+/// Com `alpha = 0`, a escolha é puramente gulosa; com `alpha = 1`, puramente aleatória.
+fn restricted_choice<R: Rng + ?Sized>(
+    candidates: &[(usize, f64)],
+    alpha: f64,
+    rng: &mut R,
+) -> usize {
+    let (min, max) = candidates
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &(_, c)| {
+            (min.min(c), max.max(c))
+        });
+    let threshold = min + alpha * (max - min);
+    let rcl: Vec<usize> = candidates
+        .iter()
+        .filter(|&&(_, c)| c <= threshold)
+        .map(|&(v, _)| v)
+        .collect();
+
+    *rcl.choose(rng)
+        .expect("The restricted candidate list is empty")
+}
+
+/// Vizinho mais próximo aleatorizado: a cada passo, o próximo vértice é sorteado dentre os não
+/// visitados cuja distância ao último vértice da rota está na lista restrita de candidatos
+/// controlada por `alpha`.
+pub fn randomized_nearest_neighbour<M, R>(
+    graph: &M,
+    start: usize,
+    alpha: f64,
+    rng: &mut R,
+) -> Solution
+where
+    M: DistanceMatrix + ?Sized,
+    R: Rng + ?Sized,
+{
+    let n = graph.node_count();
+    let mut visited = vec![false; n];
+    let mut route = Vec::with_capacity(n);
+    route.push(start);
+    visited[start] = true;
+
+    let mut candidates = Vec::with_capacity(n);
+    for _ in 1..n {
+        let last = route[route.len() - 1];
+        candidates.clear();
+        candidates.extend(
+            (0..n)
+                .filter(|&v| !visited[v])
+                .map(|v| (v, graph.cost(last, v))),
+        );
+
+        let next = restricted_choice(&candidates, alpha, rng);
+        visited[next] = true;
+        route.push(next);
+    }
+
+    let cost = Solution::calculate_cost(&route, graph);
+    Solution { route, cost }
+}
+
+/// Inserção aleatorizada: a cada passo, o vértice a ser inserido é sorteado dentre aqueles cujo
+/// custo extra de inserção (ver [`crate::heuristics::Insertion::best_position`]) está na lista
+/// restrita de candidatos controlada por `alpha`. É, portanto, uma versão aleatorizada da
+/// inserção mais barata.
+pub fn randomized_insertion<M, R>(graph: &M, start: usize, alpha: f64, rng: &mut R) -> Solution
+where
+    M: DistanceMatrix + ?Sized,
+    R: Rng + ?Sized,
+{
+    let mut candidates = Vec::with_capacity(graph.node_count());
+    insertion(graph, start, |state| {
+        candidates.clear();
+        candidates.extend(state.remaining().map(|v| (v, state.best_position(v).1)));
+        restricted_choice(&candidates, alpha, rng)
+    })
+}
+
+/// *Path-relinking* de `from` em direção a `to`.
+///
+/// # Saída
+/// A melhor solução intermediária do caminho, excluindo as extremidades, ou `None` caso as rotas
+/// difiram em menos de três posições (e não haja, portanto, solução intermediária).
+///
+/// # Detalhes de funcionamento
+/// 1. Rotaciona `to` para que comece pelo mesmo vértice que `from`;
+/// 2. Percorre as posições em ordem e, sempre que a posição `i` diverge de `to`, troca o vértice
+///    dela com o vértice que deveria estar lá, avaliando a troca em O(1) com [`Swap`];
+/// 3. Guarda a melhor solução encontrada antes de alcançar `to`.
+pub fn path_relinking<M: DistanceMatrix + ?Sized>(
+    graph: &M,
+    from: &Solution,
+    to: &Solution,
+) -> Option<Solution> {
+    let n = from.route.len();
+    let offset = to.route.iter().position(|&v| v == from.route[0])?;
+    let target: Vec<usize> = (0..n).map(|i| to.route[(offset + i) % n]).collect();
+
+    let mut position = vec![0; n];
+    for (i, &v) in from.route.iter().enumerate() {
+        position[v] = i;
+    }
+
+    let mut current = from.clone();
+    let mut remaining = (0..n).filter(|&i| current.route[i] != target[i]).count();
+    let mut best: Option<Solution> = None;
+
+    for i in 1..n {
+        if current.route[i] == target[i] {
+            continue;
+        }
+
+        let j = position[target[i]];
+        let mv = Swap { i, j };
+        let delta = mv.delta(&current.route, graph);
+        current.apply(&mv, delta);
+        position[current.route[i]] = i;
+        position[current.route[j]] = j;
+
+        remaining -= if current.route[j] == target[j] { 2 } else { 1 };
+        if remaining == 0 {
+            break;
+        }
+        if best.as_ref().is_none_or(|b| current.cost < b.cost) {
+            best = Some(current.clone());
+        }
+    }
+
+    best
+}
+
+/// Heurística construtiva aleatorizada usada pelo GRASP.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Construction {
+    NearestNeighbour,
+    Insertion,
+}
+
+impl Construction {
+    /// Constrói uma rota a partir de `start`, com a aleatoriedade controlada por `alpha`.
+    pub fn build<M, R>(self, graph: &M, start: usize, alpha: f64, rng: &mut R) -> Solution
+    where
+        M: DistanceMatrix + ?Sized,
+        R: Rng + ?Sized,
+    {
+        match self {
+            Construction::NearestNeighbour => {
+                randomized_nearest_neighbour(graph, start, alpha, rng)
+            }
+            Construction::Insertion => randomized_insertion(graph, start, alpha, rng),
+        }
+    }
+}
+
+impl FromStr for Construction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "nn" => Ok(Construction::NearestNeighbour),
+            "insertion" => Ok(Construction::Insertion),
+            s => Err(format!("unknown construction: {s}")),
+        }
+    }
+}
+
+impl fmt::Display for Construction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Construction::NearestNeighbour => write!(f, "randomized nearest neighbour"),
+            Construction::Insertion => write!(f, "randomized insertion"),
+        }
+    }
+}
+
+/// Parâmetros do GRASP.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grasp {
+    /// Heurística construtiva aleatorizada.
+    pub construction: Construction,
+    /// Tamanho relativo da lista restrita de candidatos, entre 0 (guloso) e 1 (aleatório).
+    pub alpha: f64,
+    /// Buscas locais aplicadas, via [`vnd`], a cada rota construída.
+    pub local_search: Vec<Neighbourhood>,
+    /// Critério de parada.
+    pub budget: Budget,
+    /// Quantidade de soluções elite usadas no *path-relinking*. Com `0`, não há *path-relinking*.
+    pub elite_size: usize,
+}
+
+impl Grasp {
+    /// Executa o GRASP e retorna a melhor solução encontrada.
+    ///
+    /// # Detalhes de funcionamento
+    /// 1. Constrói uma rota a partir de um vértice inicial sorteado, com [`Construction::build`];
+    /// 2. Aplica a busca local;
+    /// 3. Caso haja soluções elite, faz o *path-relinking* da rota em direção a uma delas,
+    ///    sorteada, e aplica a busca local na melhor solução intermediária, ficando com ela caso
+    ///    seja melhor que a rota;
+    /// 4. Atualiza o conjunto elite: a rota entra caso não esteja nele e o conjunto não esteja
+    ///    cheio ou ela seja melhor que a pior solução elite, que sai;
+    /// 5. Repete até o fim do orçamento, retornando a melhor solução. Mesmo com um orçamento nulo,
+    ///    ao menos uma rota é construída e levada a um ótimo local.
+    pub fn run<M, R>(&self, graph: &M, rng: &mut R) -> Solution
+    where
+        M: DistanceMatrix + ?Sized,
+        R: Rng + ?Sized,
+    {
+        let n = graph.node_count();
+        let started = Instant::now();
+        let candidates = CandidateLists::new(graph, &self.local_search);
+        let mut elite: Vec<Solution> = Vec::with_capacity(self.elite_size);
+        let mut best: Option<Solution> = None;
+        let mut iteration = 0;
+
+        while iteration == 0 || !self.budget.exhausted(iteration, started) {
+            iteration += 1;
+
+            let start = rng.random_range(0..n);
+            let built = self.construction.build(graph, start, self.alpha, rng);
+            let mut solution = vnd(&built, graph, &self.local_search, &candidates);
+
+            if let Some(guide) = elite.choose(rng)
+                && let Some(relinked) = path_relinking(graph, &solution, guide)
+            {
+                let relinked = vnd(&relinked, graph, &self.local_search, &candidates);
+                if relinked.cost < solution.cost - EPSILON {
+                    solution = relinked;
+                }
+            }
+
+            if self.elite_size > 0 {
+                update_elite(&mut elite, &solution, self.elite_size);
+            }
+            if best.as_ref().is_none_or(|b| solution.cost < b.cost) {
+                best = Some(solution);
+            }
+        }
+
+        best.expect("GRASP always runs at least one iteration")
+    }
+}
+
+/// Insere `solution` no conjunto `elite` de no máximo `size` soluções, caso ela ainda não esteja
+/// nele e não seja pior que todas as soluções elite.
+fn update_elite(elite: &mut Vec<Solution>, solution: &Solution, size: usize) {
+    let n = solution.route.len();
+    let same_tour = |s: &Solution| {
+        let offset = s.route.iter().position(|&v| v == solution.route[0]);
+        offset.is_some_and(|offset| (0..n).all(|i| s.route[(offset + i) % n] == solution.route[i]))
+    };
+    if elite.iter().any(same_tour) {
+        return;
+    }
+
+    if elite.len() < size {
+        elite.push(solution.clone());
+    } else if let Some(worst) = elite.iter_mut().max_by(|a, b| a.cost.total_cmp(&b.cost))
+        && solution.cost < worst.cost
+    {
+        *worst = solution.clone();
+    }
+}
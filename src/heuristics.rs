@@ -0,0 +1,380 @@
+//! Heurísticas construtivas para o Problema do Caixeiro Viajante.
+//!
+//! As heurísticas de vizinho mais próximo e de inserção crescem a rota vértice a vértice, enquanto
+//! a gulosa de arestas e a de Clarke–Wright constroem a rota aresta a aresta.
+
+use rand::{Rng, seq::SliceRandom};
+
+use crate::{disjoint_set::DisjointSet, local_search::Solution, matrix::DistanceMatrix};
+
+/// Implementação da heurística do Vizinho Mais Próximo (Nearest Neighbor), um algoritmo
+/// guloso que gera um caminho para o Problema do Caixeiro Viajante.
+///
+/// # Argumentos
+/// - `graph`: grafo representado em matriz de adjacência;
+/// - `start`: nó inicial por onde o caminho começará.
+///
+/// # Saída
+/// Retorna uma `Solution`, com a rota encontrada e o custo dela.
+///
+/// # Detalhes de funcionamento
+/// 1. Cria um vetor booleano `visited`, onde é possível verificar em O(1) se um
+///    vértice já foi visitado. Todos começam como "não visitados".
+/// 2. Marca o vértice inicial como visitado.
+/// 3. Cria um contador com a quantidade de vértices visitados. Isso permite
+///    determinar o fim do loop sem precisar usar uma checagem O(n).
+/// 4. Enquanto houver vértices não visitados, itera sobre eles na matriz em busca
+///    da aresta de menor custo. Ao encontrá-la, adiciona seu vértice adjacente ao
+///    caminho, marca-o como visitado e incrementa o contador.
+/// 5. Ao fim do loop, adiciona ao custo a aresta que liga o último vértice do
+///    caminho ao vértice inicial, sem adicionar este novamente ao caminho.
+/// 6. Retorna o caminho encontrado e o custo total.
+///
+/// Observação: este algoritmo **não** insere o vértice inicial no fim do caminho,
+/// pois assume-se que há um ciclo hamiltoniano implícito; portanto, adicionar o
+/// vértice inicial novamente não é necessário.
+pub fn nearest_neighbour<M: DistanceMatrix + ?Sized>(graph: &M, start: usize) -> Solution {
+    let mut visited: Vec<bool> = vec![false; graph.node_count()];
+    let mut path: Vec<usize> = Vec::new();
+    path.push(start);
+
+    let mut cost: f64 = 0.0;
+    let mut current_node = start;
+    visited[current_node] = true;
+
+    let mut visited_count = 1;
+
+    let mut better_cost;
+    let mut next_on_path: Option<usize> = None;
+
+    while visited_count != graph.node_count() {
+        better_cost = f64::INFINITY;
+
+        for (i, val) in visited.iter().enumerate() {
+            if *val || current_node == i {
+                continue;
+            }
+
+            if graph.cost(current_node, i) < better_cost {
+                better_cost = graph.cost(current_node, i);
+                next_on_path = Some(i);
+            }
+        }
+
+        if let Some(next_on_path) = next_on_path {
+            let n = next_on_path;
+            path.push(n);
+            cost += better_cost;
+            current_node = n;
+            visited[n] = true;
+            visited_count += 1;
+        }
+    }
+
+    cost += graph.cost(current_node, start);
+
+    Solution { route: path, cost }
+}
+
+/// Estado de uma heurística de inserção: o ciclo parcial e, para cada vértice fora dele, a
+/// distância até o vértice mais próximo do ciclo.
+pub struct Insertion<'a, M: DistanceMatrix + ?Sized> {
+    graph: &'a M,
+    cycle: Vec<usize>,
+    in_cycle: Vec<bool>,
+    min_dist: Vec<f64>,
+}
+
+impl<'a, M: DistanceMatrix + ?Sized> Insertion<'a, M> {
+    fn new(graph: &'a M, start: usize) -> Self {
+        let n = graph.node_count();
+        let mut in_cycle = vec![false; n];
+        in_cycle[start] = true;
+        let min_dist = (0..n).map(|v| graph.cost(v, start)).collect();
+
+        Self {
+            graph,
+            cycle: vec![start],
+            in_cycle,
+            min_dist,
+        }
+    }
+
+    /// Vértices que ainda não estão no ciclo.
+    pub fn remaining(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.in_cycle.len()).filter(|&v| !self.in_cycle[v])
+    }
+
+    /// Menor distância entre `v` e algum vértice do ciclo.
+    pub fn distance(&self, v: usize) -> f64 {
+        self.min_dist[v]
+    }
+
+    /// Posição do ciclo em que inserir `v` gera o menor custo extra, junto desse custo. Inserir
+    /// `v` na posição `i` troca a aresta `(cycle[i - 1], cycle[i])` por `(cycle[i - 1], v)` e
+    /// `(v, cycle[i])`.
+    pub fn best_position(&self, v: usize) -> (usize, f64) {
+        let d = |a: usize, b: usize| self.graph.cost(a, b);
+        let len = self.cycle.len();
+
+        (0..len)
+            .map(|i| {
+                let (a, b) = (self.cycle[i], self.cycle[(i + 1) % len]);
+                (i + 1, d(a, v) + d(v, b) - d(a, b))
+            })
+            .fold((len, f64::INFINITY), |best, (i, extra)| {
+                if extra < best.1 { (i, extra) } else { best }
+            })
+    }
+
+    /// Insere `v` na posição de menor custo extra e atualiza as distâncias dos vértices fora do
+    /// ciclo em O(n).
+    fn insert(&mut self, v: usize) {
+        let (position, _) = self.best_position(v);
+        self.cycle.insert(position, v);
+        self.in_cycle[v] = true;
+
+        for u in 0..self.in_cycle.len() {
+            if !self.in_cycle[u] {
+                self.min_dist[u] = self.min_dist[u].min(self.graph.cost(u, v));
+            }
+        }
+    }
+}
+
+/// Estrutura comum às heurísticas de inserção.
+///
+/// # Argumentos
+/// - `graph`: grafo representado em matriz de adjacência;
+/// - `start`: vértice que forma o ciclo inicial;
+/// - `select`: critério que escolhe, a cada passo, o próximo vértice a ser inserido dentre os que
+///   ainda estão fora do ciclo.
+///
+/// # Saída
+/// Uma `Solution` com o ciclo construído, iniciado em `start`.
+///
+/// # Detalhes de funcionamento
+/// 1. O ciclo começa apenas com o vértice `start`;
+/// 2. Enquanto houver vértices fora do ciclo, `select` escolhe o próximo vértice `r*`;
+/// 3. Testa-se todas as arestas consecutivas `(u, v)` do ciclo a fim de encontrar a posição que
+///    minimize o custo adicional `d(u, r*) + d(r*, v) − d(u, v)`, e `r*` é inserido nela;
+/// 4. As distâncias dos vértices restantes até o ciclo são atualizadas em O(n).
+pub fn insertion<M, F>(graph: &M, start: usize, mut select: F) -> Solution
+where
+    M: DistanceMatrix + ?Sized,
+    F: FnMut(&Insertion<M>) -> usize,
+{
+    let mut state = Insertion::new(graph, start);
+
+    for _ in 1..graph.node_count() {
+        let r_star = select(&state);
+        state.insert(r_star);
+    }
+
+    let cost = Solution::calculate_cost(&state.cycle, graph);
+    Solution {
+        route: state.cycle,
+        cost,
+    }
+}
+
+/// Implementação da Heurística de Inserção Mais Próxima (*Nearest Insertion*), um algoritmo
+/// guloso utilizado para gerar um ciclo aproximado para o Problema do Caixeiro Viajante (TSP).
+///
+/// A cada passo, insere o vértice cuja distância ao ciclo é mínima (ver [`insertion`]). O primeiro
+/// vértice inserido é, portanto, o vizinho mais próximo de `start`, formando um ciclo inicial
+/// mínimo do tipo `start → v → start`.
+pub fn nearest_insertion<M: DistanceMatrix + ?Sized>(graph: &M, start: usize) -> Solution {
+    insertion(graph, start, |state| {
+        state
+            .remaining()
+            .fold((None, f64::INFINITY), |best, v| {
+                if state.distance(v) < best.1 {
+                    (Some(v), state.distance(v))
+                } else {
+                    best
+                }
+            })
+            .0
+            .expect("No candidate vertex found")
+    })
+}
+
+/// Heurística de Inserção Mais Distante (*Farthest Insertion*).
+///
+/// A cada passo, insere o vértice cuja distância ao ciclo é máxima (ver [`insertion`]), de forma
+/// que o formato geral do ciclo é definido cedo pelos vértices mais afastados.
+pub fn farthest_insertion<M: DistanceMatrix + ?Sized>(graph: &M, start: usize) -> Solution {
+    insertion(graph, start, |state| {
+        state
+            .remaining()
+            .fold((None, f64::NEG_INFINITY), |best, v| {
+                if state.distance(v) > best.1 {
+                    (Some(v), state.distance(v))
+                } else {
+                    best
+                }
+            })
+            .0
+            .expect("No candidate vertex found")
+    })
+}
+
+/// Heurística de Inserção Mais Barata (*Cheapest Insertion*).
+///
+/// A cada passo, insere o vértice cuja melhor posição de inserção tem o menor custo extra dentre
+/// todos os vértices fora do ciclo (ver [`insertion`]), custando O(n²) por passo.
+pub fn cheapest_insertion<M: DistanceMatrix + ?Sized>(graph: &M, start: usize) -> Solution {
+    insertion(graph, start, |state| {
+        state
+            .remaining()
+            .fold((None, f64::INFINITY), |best, v| {
+                let (_, extra) = state.best_position(v);
+                if extra < best.1 {
+                    (Some(v), extra)
+                } else {
+                    best
+                }
+            })
+            .0
+            .expect("No candidate vertex found")
+    })
+}
+
+/// Heurística de Inserção Aleatória (*Random Insertion*).
+///
+/// Os vértices são inseridos, cada um na sua melhor posição, em uma ordem sorteada com `rng` (ver
+/// [`insertion`]).
+pub fn random_insertion<M, R>(graph: &M, start: usize, rng: &mut R) -> Solution
+where
+    M: DistanceMatrix + ?Sized,
+    R: Rng + ?Sized,
+{
+    let mut order: Vec<usize> = (0..graph.node_count()).filter(|&v| v != start).collect();
+    order.shuffle(rng);
+    let mut order = order.into_iter();
+
+    insertion(graph, start, |_| {
+        order.next().expect("No candidate vertex found")
+    })
+}
+
+/// Heurística de Inserção Arbitrária (*Arbitrary Insertion*).
+///
+/// Os vértices são inseridos, cada um na sua melhor posição, na ordem dos seus índices (ver
+/// [`insertion`]). Diferente da [`random_insertion`], é determinística.
+pub fn arbitrary_insertion<M: DistanceMatrix + ?Sized>(graph: &M, start: usize) -> Solution {
+    insertion(graph, start, |state| {
+        state.remaining().next().expect("No candidate vertex found")
+    })
+}
+
+/// Une os vértices de `nodes` em um único caminho, adicionando as arestas na ordem em que
+/// aparecem em `edges`, desde que ambos os extremos ainda tenham grau menor que 2 e estejam em
+/// fragmentos diferentes (i.e., a aresta não fecha um ciclo).
+fn greedy_path(n: usize, nodes: &[usize], edges: &[(usize, usize)]) -> Vec<usize> {
+    let mut adjacent: Vec<Vec<usize>> = vec![Vec::with_capacity(2); n];
+    let mut fragments = DisjointSet::new(n);
+    let mut added = 0;
+
+    for &(u, v) in edges {
+        if added + 1 >= nodes.len() {
+            break;
+        }
+        if adjacent[u].len() < 2 && adjacent[v].len() < 2 && fragments.union(u, v) {
+            adjacent[u].push(v);
+            adjacent[v].push(u);
+            added += 1;
+        }
+    }
+
+    let Some(&first) = nodes.iter().find(|&&v| adjacent[v].len() < 2) else {
+        return Vec::new();
+    };
+
+    let mut path = vec![first];
+    let mut previous = None;
+    let mut current = first;
+    while let Some(&next) = adjacent[current].iter().find(|&&v| Some(v) != previous) {
+        path.push(next);
+        previous = Some(current);
+        current = next;
+    }
+
+    path
+}
+
+/// Heurística gulosa de arestas (*Greedy Edge*).
+///
+/// # Argumentos
+/// - `graph`: grafo representado em matriz de adjacência.
+///
+/// # Saída
+/// Uma `Solution` com o ciclo construído.
+///
+/// # Detalhes de funcionamento
+/// 1. Ordena todas as arestas `(u, v)` pelo custo, da mais barata para a mais cara;
+/// 2. Percorre as arestas nessa ordem, adicionando-as à solução sempre que `u` e `v` ainda tiverem
+///    grau menor que 2 e estiverem em fragmentos diferentes, verificado em O(α(n)) com conjuntos
+///    disjuntos. Assim, a solução é sempre um conjunto de caminhos disjuntos;
+/// 3. Após adicionar `n - 1` arestas, resta um único caminho hamiltoniano, que é fechado ligando
+///    seus extremos.
+///
+/// Observação: em matrizes assimétricas, o custo de uma aresta é a média dos custos nos dois
+/// sentidos.
+pub fn greedy_edge<M: DistanceMatrix + ?Sized>(graph: &M) -> Solution {
+    let n = graph.node_count();
+    let weight = |u: usize, v: usize| (graph.cost(u, v) + graph.cost(v, u)) / 2.0;
+
+    let mut edges: Vec<(usize, usize)> = (0..n)
+        .flat_map(|u| (u + 1..n).map(move |v| (u, v)))
+        .collect();
+    edges.sort_by(|&(a, b), &(u, v)| weight(a, b).total_cmp(&weight(u, v)));
+
+    let nodes: Vec<usize> = (0..n).collect();
+    let route = greedy_path(n, &nodes, &edges);
+    let cost = Solution::calculate_cost(&route, graph);
+
+    Solution { route, cost }
+}
+
+/// Heurística das economias de Clarke–Wright (*Savings*), com `hub` como vértice central.
+///
+/// # Argumentos
+/// - `graph`: grafo representado em matriz de adjacência;
+/// - `hub`: vértice central, pelo qual todas as rotas passam inicialmente.
+///
+/// # Saída
+/// Uma `Solution` com o ciclo construído, iniciado em `hub`.
+///
+/// # Detalhes de funcionamento
+/// 1. Começa com uma rota `hub → v → hub` para cada vértice `v`;
+/// 2. Calcula a economia de ligar diretamente `u` e `v` em vez de passar pelo `hub`:
+///    `s(u, v) = d(u, hub) + d(hub, v) − d(u, v)`;
+/// 3. Percorre os pares em ordem decrescente de economia, juntando as rotas de `u` e `v` sempre
+///    que ambos forem extremos de rotas diferentes;
+/// 4. Ao fim, resta uma única rota, cujos extremos são ligados ao `hub`.
+///
+/// Observação: em matrizes assimétricas, a economia de um par é a média das economias nos dois
+/// sentidos.
+pub fn clarke_wright<M: DistanceMatrix + ?Sized>(graph: &M, hub: usize) -> Solution {
+    let n = graph.node_count();
+    let d = |u: usize, v: usize| graph.cost(u, v);
+    let saving = |u: usize, v: usize| {
+        (d(u, hub) + d(hub, v) - d(u, v) + d(v, hub) + d(hub, u) - d(v, u)) / 2.0
+    };
+
+    let nodes: Vec<usize> = (0..n).filter(|&v| v != hub).collect();
+    let mut edges: Vec<(usize, usize)> = nodes
+        .iter()
+        .enumerate()
+        .flat_map(|(i, &u)| nodes[i + 1..].iter().map(move |&v| (u, v)))
+        .collect();
+    edges.sort_by(|&(a, b), &(u, v)| saving(u, v).total_cmp(&saving(a, b)));
+
+    let mut route = vec![hub];
+    route.extend(greedy_path(n, &nodes, &edges));
+    let cost = Solution::calculate_cost(&route, graph);
+
+    Solution { route, cost }
+}
@@ -0,0 +1,180 @@
+//! Busca Local Iterada (*Iterated Local Search*, ILS) para o Problema do Caixeiro Viajante.
+
+use std::{fmt, str::FromStr, time::Instant};
+
+use rand::{Rng, seq::SliceRandom};
+
+use crate::{
+    budget::Budget,
+    local_search::{
+        EPSILON, Solution,
+        vnd::{CandidateLists, Neighbourhood, vnd},
+    },
+    matrix::DistanceMatrix,
+};
+
+/// Perturbação aplicada ao ótimo local corrente a cada iteração (o *kick*).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Perturbation {
+    /// Corta a rota `A B C D` em quatro segmentos e os reconecta como `A C B D`, um movimento de
+    /// 4-opt que o 2-opt e o 3-opt sequencial não conseguem desfazer.
+    DoubleBridge,
+    /// Inverte um segmento aleatório da rota.
+    SegmentReversal,
+}
+
+impl Perturbation {
+    /// Aplica a perturbação em `solution`, recalculando o custo.
+    pub fn apply<M, R>(self, solution: &mut Solution, graph: &M, rng: &mut R)
+    where
+        M: DistanceMatrix + ?Sized,
+        R: Rng + ?Sized,
+    {
+        let route = &mut solution.route;
+        let n = route.len();
+        if n < 4 {
+            return;
+        }
+
+        match self {
+            Perturbation::DoubleBridge => {
+                let mut cuts = rand::seq::index::sample(rng, n - 1, 3).into_vec();
+                cuts.sort_unstable();
+                let (a, b, c) = (cuts[0] + 1, cuts[1] + 1, cuts[2] + 1);
+                // A = [0, a), B = [a, b), C = [b, c), D = [c, n).
+                route[a..c].rotate_left(b - a);
+            }
+            Perturbation::SegmentReversal => {
+                let i = rng.random_range(0..n - 1);
+                let j = rng.random_range(i + 1..n);
+                route[i..=j].reverse();
+            }
+        }
+
+        solution.cost = Solution::calculate_cost(route, graph);
+    }
+}
+
+impl FromStr for Perturbation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "double-bridge" => Ok(Perturbation::DoubleBridge),
+            "reversal" => Ok(Perturbation::SegmentReversal),
+            s => Err(format!("unknown perturbation: {s}")),
+        }
+    }
+}
+
+impl fmt::Display for Perturbation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Perturbation::DoubleBridge => write!(f, "double-bridge"),
+            Perturbation::SegmentReversal => write!(f, "reversal"),
+        }
+    }
+}
+
+/// Critério que decide se o novo ótimo local substitui o corrente.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Acceptance {
+    /// Aceita apenas soluções melhores que a corrente.
+    Better,
+    /// Aceita sempre a nova solução (passeio aleatório entre ótimos locais).
+    RandomWalk,
+    /// Aceita apenas soluções melhores, mas reinicia de uma rota aleatória após a quantidade dada
+    /// de perturbações seguidas sem melhora.
+    Restart(usize),
+}
+
+impl FromStr for Acceptance {
+    type Err = String;
+
+    /// Lê `better`, `walk` ou `restart:K`, onde `K` é a quantidade de perturbações sem melhora
+    /// antes do reinício.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().split_once(':') {
+            None if s.trim() == "better" => Ok(Acceptance::Better),
+            None if s.trim() == "walk" => Ok(Acceptance::RandomWalk),
+            Some(("restart", k)) => k
+                .parse()
+                .map(Acceptance::Restart)
+                .map_err(|_| format!("invalid number of kicks: {k}")),
+            _ => Err(format!("unknown acceptance: {s}")),
+        }
+    }
+}
+
+impl fmt::Display for Acceptance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Acceptance::Better => write!(f, "better"),
+            Acceptance::RandomWalk => write!(f, "walk"),
+            Acceptance::Restart(k) => write!(f, "restart:{k}"),
+        }
+    }
+}
+
+/// Parâmetros da Busca Local Iterada.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ils {
+    pub perturbation: Perturbation,
+    pub acceptance: Acceptance,
+    /// Buscas locais aplicadas, via [`vnd`], após cada perturbação.
+    pub local_search: Vec<Neighbourhood>,
+    /// Critério de parada, em quantidade de perturbações ou tempo.
+    pub budget: Budget,
+}
+
+impl Ils {
+    /// Executa a ILS a partir de `initial` e retorna a melhor solução encontrada.
+    ///
+    /// # Detalhes de funcionamento
+    /// 1. Leva `initial` a um ótimo local com a busca local;
+    /// 2. Perturba uma cópia da solução corrente e a leva a um ótimo local;
+    /// 3. Decide, com o critério de aceitação, se ela substitui a corrente. No caso de
+    ///    [`Acceptance::Restart`], a corrente é trocada por uma rota aleatória, levada a um ótimo
+    ///    local, após `k` perturbações seguidas sem melhora;
+    /// 4. Repete até o fim do orçamento, retornando a melhor solução.
+    pub fn run<M, R>(&self, initial: &Solution, graph: &M, rng: &mut R) -> Solution
+    where
+        M: DistanceMatrix + ?Sized,
+        R: Rng + ?Sized,
+    {
+        let started = Instant::now();
+        let candidates = CandidateLists::new(graph, &self.local_search);
+        let mut current = vnd(initial, graph, &self.local_search, &candidates);
+        let mut best = current.clone();
+        let mut iteration = 0;
+        let mut stale = 0;
+
+        while !self.budget.exhausted(iteration, started) {
+            iteration += 1;
+
+            let mut candidate = current.clone();
+            self.perturbation.apply(&mut candidate, graph, rng);
+            let candidate = vnd(&candidate, graph, &self.local_search, &candidates);
+
+            let improved = candidate.cost < current.cost - EPSILON;
+            stale = if improved { 0 } else { stale + 1 };
+            if candidate.cost < best.cost - EPSILON {
+                best = candidate.clone();
+            }
+
+            match self.acceptance {
+                Acceptance::Better | Acceptance::Restart(_) if improved => current = candidate,
+                Acceptance::RandomWalk => current = candidate,
+                Acceptance::Restart(k) if stale >= k => {
+                    current.route.shuffle(rng);
+                    current.cost = Solution::calculate_cost(&current.route, graph);
+                    current = vnd(&current, graph, &self.local_search, &candidates);
+                    stale = 0;
+                }
+                _ => {}
+            }
+        }
+
+        best
+    }
+}
@@ -0,0 +1,140 @@
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::Path,
+};
+
+use crate::matrix::Matrix;
+
+/// Carrega, em tempo de execução, uma instância no mesmo formato lido por `graph_from_csv!`.
+///
+/// # Argumentos
+/// - `path`: caminho para o csv da instância (e.g. `data/001/data.csv`).
+///
+/// # Saída
+/// Retorna a matriz de adjacência da instância, ou um erro caso o arquivo não possa ser lido ou
+/// não esteja no formato esperado.
+///
+/// # Formato
+/// - A primeira linha é um cabeçalho com os rótulos dos vértices e é ignorada;
+/// - A primeira coluna de cada linha é o rótulo do vértice e também é ignorada;
+/// - Células vazias (como a diagonal) são lidas como `0`;
+/// - A matriz precisa ser quadrada.
+pub fn load(path: impl AsRef<Path>) -> io::Result<Matrix> {
+    let file = BufReader::new(File::open(path)?);
+    let mut rows = Vec::new();
+
+    for (n, line) in file.lines().enumerate().skip(1) {
+        let line = line?;
+        let line = line.trim_end();
+        if line.is_empty() {
+            continue;
+        }
+
+        let row = line
+            .split(',')
+            .skip(1)
+            .map(|record| match record.trim() {
+                "" => Ok(0.0),
+                r => r.parse().map_err(|_| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("invalid weight {r:?} at line {}", n + 1),
+                    )
+                }),
+            })
+            .collect::<io::Result<Vec<f64>>>()?;
+        rows.push(row);
+    }
+
+    Matrix::from_rows(rows)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "adjacency matrix is not square"))
+}
+
+/// Nome do arquivo, na pasta da instância, com o seu valor de referência.
+pub const REFERENCE_FILE: &str = "reference.txt";
+
+/// Custo de referência de uma instância.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Reference {
+    /// Custo ótimo provado, e.g. pelos algoritmos exatos.
+    Optimal(f64),
+    /// Melhor custo conhecido, sem prova de otimalidade.
+    Best(f64),
+}
+
+impl Reference {
+    pub fn value(&self) -> f64 {
+        match *self {
+            Reference::Optimal(value) | Reference::Best(value) => value,
+        }
+    }
+
+    /// Distância relativa de `cost` até a referência: `(cost − referência) / referência`.
+    /// Negativa caso `cost` seja melhor que o melhor custo conhecido.
+    pub fn gap(&self, cost: f64) -> f64 {
+        (cost - self.value()) / self.value()
+    }
+}
+
+/// Lê o valor de referência da instância, guardado no arquivo [`REFERENCE_FILE`] da mesma pasta
+/// do csv. O arquivo tem uma única linha `optimal <custo>` ou `best <custo>`.
+///
+/// # Saída
+/// `None` caso a instância não tenha arquivo de referência, ou um erro caso ele não esteja no
+/// formato esperado.
+pub fn load_reference(path: impl AsRef<Path>) -> io::Result<Option<Reference>> {
+    let path = path.as_ref().with_file_name(REFERENCE_FILE);
+    let content = match std::fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    };
+
+    let invalid = || {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "invalid reference in {}: {:?}",
+                path.display(),
+                content.trim()
+            ),
+        )
+    };
+    let (kind, value) = content
+        .trim()
+        .split_once(char::is_whitespace)
+        .ok_or_else(invalid)?;
+    let value: f64 = value.trim().parse().map_err(|_| invalid())?;
+
+    match kind {
+        "optimal" => Ok(Some(Reference::Optimal(value))),
+        "best" => Ok(Some(Reference::Best(value))),
+        _ => Err(invalid()),
+    }
+}
+
+/// Distância relativa, em porcentagem, entre `cost` e o valor de referência da instância em
+/// `path`, caso ela tenha um (ver [`load_reference`]).
+///
+/// # Panics
+/// Caso `cost` seja menor que um ótimo provado, o que indica um erro no cálculo do custo ou na
+/// referência, ou caso o arquivo de referência seja inválido.
+pub fn reference_gap(path: impl AsRef<Path>, cost: f64) -> Option<f64> {
+    let path = path.as_ref();
+    let reference = load_reference(path).unwrap_or_else(|e| panic!("{e}"))?;
+
+    let gap = 100.0 * reference.gap(cost);
+    match reference {
+        Reference::Optimal(optimum) => {
+            assert!(
+                cost >= optimum - 1e-6,
+                "cost {cost} is below the proven optimum {optimum} of {}",
+                path.display()
+            );
+            // Ignora a diferença de arredondamento entre o custo e o ótimo.
+            Some(gap.max(0.0))
+        }
+        Reference::Best(_) => Some(gap),
+    }
+}
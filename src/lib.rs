@@ -3,17 +3,45 @@
 //! Crate que fornece algoritmos e estruturas de dados para Grafos.
 //!
 //! A organização do crate é feita em diversos módulos:
-//! - `adjacency_list`: guarda a representação de um grafo no formato da Lista de Adjacência,
-//!   bastante popular e comum de implementar;
-//! - `traversal`: armazena os algoritmos para a travessia em um grafo. Os algoritmos incluem a
-//!   BFS, DFS, DFS com classificação de arestas e identificação de componentes.
+//! - `matrix`: matriz de adjacência com tamanho definido em tempo de execução;
+//! - `instance`: leitura das instâncias em `data/` em tempo de execução, sem precisar recompilar
+//!   os binários para cada instância;
+//! - `aco`: Otimização por Colônia de Formigas, nas variantes Ant System e MAX-MIN;
+//! - `annealing`: Têmpera Simulada com resfriamento geométrico, linear ou adaptativo;
+//! - `budget`: critério de parada (iterações ou tempo) das metaheurísticas;
+//! - `exact`: algoritmos exatos (Held–Karp e *branch-and-bound*);
+//! - `grasp`: a metaheurística GRASP, com *path-relinking* opcional;
+//! - `heuristics`: heurísticas construtivas para o Problema do Caixeiro Viajante (vizinho mais
+//!   próximo, a família das heurísticas de inserção, gulosa de arestas e Clarke–Wright);
+//! - `ils`: Busca Local Iterada com perturbação *double-bridge* ou inversão de segmento;
+//! - `local_search`: buscas locais sobre uma solução e a descida em vizinhança variável (VND);
+//! - `lower_bound`: limite inferior de Held–Karp (1-tree com penalidades ajustadas por
+//!   subgradiente) e o cálculo do *gap* de otimalidade;
+//! - `mst`: árvores geradoras mínimas (Prim e Kruskal), 1-trees e as heurísticas da árvore dupla e de
+//!   Christofides;
+//! - `multi_start`: execução de uma heurística a partir de todos (ou de uma amostra) dos vértices
+//!   iniciais;
+//! - `tabu`: Busca Tabu sobre as vizinhanças 2-opt e swap, com aspiração e memória de frequência.
 
 #![feature(impl_trait_in_assoc_type)]
 
-// mod heuristics;
+pub mod aco;
+pub mod annealing;
+pub mod budget;
+mod disjoint_set;
+pub mod exact;
+pub mod grasp;
+pub mod heuristics;
+pub mod ils;
+pub mod instance;
 pub mod local_search;
+pub mod lower_bound;
+pub mod matrix;
+pub mod mst;
+pub mod multi_start;
+pub mod tabu;
 
 pub mod graphs {
-    // pub use crate::heuristics::nearest_neighbour;
-    pub type Graph = Vec<Vec<f64>>;
+    pub use crate::heuristics::nearest_neighbour;
+    pub type Graph = crate::matrix::Matrix;
 }
@@ -1,218 +1,376 @@
+pub mod candidates;
+mod lin_kernighan;
+pub mod moves;
+pub mod vnd;
+
+use crate::matrix::DistanceMatrix;
+use candidates::Candidates;
+use moves::{Move, OrOpt, Swap, ThreeOpt, TwoOpt};
+
+/// Tolerância usada para decidir se um movimento de fato melhora a solução. Evita que erros de
+/// arredondamento no cálculo das variações façam a busca ciclar entre soluções de mesmo custo.
+pub(crate) const EPSILON: f64 = 1e-9;
+
+/// Regra de pivoteamento de uma busca local, i.e., qual movimento aplicar dentre os que melhoram a
+/// solução.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pivot {
+    /// Aplica o primeiro movimento de melhora encontrado na vizinhança.
+    FirstImprovement,
+    /// Avalia toda a vizinhança e aplica o movimento de maior melhora.
+    BestImprovement,
+}
+
 #[allow(dead_code)]
-pub trait LocalSearch<Graph> {
-    fn swap(&self, graph: &Graph, start: usize) -> Self;
-    fn two_opt(&self, graph: &Graph) -> Self;
-    fn shift(&self, graph: &Graph, start: usize) -> Self;
-    fn or_opt(&self, graph: &Graph) -> Self;
+pub trait LocalSearch<Graph: ?Sized> {
+    /// Aplica trocas de posições até atingir um ótimo local. Com `anchor = None`, a vizinhança é
+    /// formada pela troca de todos os pares de posições `(i, j)`; com `Some(p)`, apenas trocas
+    /// envolvendo a posição `p` são consideradas.
+    fn swap(&self, graph: &Graph, anchor: Option<usize>) -> Self;
+    /// Aplica movimentos 2-opt até atingir um ótimo local, seguindo a regra de pivoteamento
+    /// `pivot`. Retorna a solução encontrada e a quantidade de movimentos aplicados.
+    fn two_opt(&self, graph: &Graph, pivot: Pivot) -> (Self, usize)
+    where
+        Self: Sized;
+    /// Aplica realocações de um único vértice até atingir um ótimo local. Com `anchor = None`, a
+    /// vizinhança move o vértice de cada posição para qualquer outra; com `Some(p)`, apenas o
+    /// vértice da posição `p` é movido.
+    fn shift(&self, graph: &Graph, anchor: Option<usize>) -> Self;
+    /// Aplica movimentos or-opt até atingir um ótimo local. A vizinhança move sequências de até
+    /// `max_len` vértices consecutivos para outra posição da rota, tanto na orientação original
+    /// quanto invertidas.
+    fn or_opt(&self, graph: &Graph, max_len: usize) -> Self;
+    /// Aplica movimentos 3-opt, considerando todas as reconexões das três arestas removidas, até
+    /// atingir um ótimo local.
+    fn three_opt(&self, graph: &Graph) -> Self;
+    /// Aplica movimentos or-3opt, i.e., trocas de dois trechos consecutivos da rota mantendo a
+    /// orientação de ambos, até atingir um ótimo local.
+    fn or3opt(&self, graph: &Graph) -> Self;
+    /// Aplica uma busca de profundidade variável no estilo Lin–Kernighan, encadeando até
+    /// `max_depth` movimentos 2-opt sequenciais a partir de cada vértice e orientação da rota, até
+    /// que nenhuma sequência melhore a solução.
+    fn lin_kernighan(&self, graph: &Graph, max_depth: usize) -> Self;
+    /// Aplica movimentos 2-opt até atingir um ótimo local, avaliando apenas os movimentos que
+    /// adicionam arestas entre um vértice e seus `candidates`, com *don't-look bits*. Com
+    /// `candidates = None`, volta a explorar a vizinhança completa, como [`LocalSearch::two_opt`].
+    fn two_opt_candidates(&self, graph: &Graph, candidates: Option<&Candidates>) -> Self;
+    /// Igual a [`LocalSearch::or_opt`], mas avaliando apenas reinserções ao lado dos `candidates`
+    /// das extremidades de cada sequência, com *don't-look bits*. Com `candidates = None`, volta a
+    /// explorar a vizinhança completa.
+    fn or_opt_candidates(
+        &self,
+        graph: &Graph,
+        max_len: usize,
+        candidates: Option<&Candidates>,
+    ) -> Self;
 }
 
-#[derive(PartialEq, Clone)]
+#[derive(PartialEq, Clone, Debug)]
 #[allow(dead_code)]
-pub struct Solution<const N: usize> {
+pub struct Solution {
     pub route: Vec<usize>,
     pub cost: f64,
 }
 
 #[allow(dead_code)]
-impl<const N: usize> Solution<N> {
-    pub fn calculate_cost(route: &[usize], graph: &[[f64; N]; N]) -> f64 {
+impl Solution {
+    pub fn calculate_cost<M: DistanceMatrix + ?Sized>(route: &[usize], graph: &M) -> f64 {
         if route.is_empty() {
             return 0.0;
         }
 
-        route.windows(2).map(|w| graph[w[0]][w[1]]).sum::<f64>()
-            + graph[route[route.len() - 1]][route[0]]
+        route
+            .windows(2)
+            .map(|w| graph.cost(w[0], w[1]))
+            .sum::<f64>()
+            + graph.cost(route[route.len() - 1], route[0])
     }
 
-    fn neighbourhood_by_swap(&self, graph: &[[f64; N]; N], start: usize) -> Vec<Self> {
-        let mut solutions: Vec<Solution<N>> = Vec::new();
-
-        for v in &self.route {
-            let mut new_route = self.route.clone();
-
-            new_route.swap(start, *v);
-            // let tmp = new_route[start];
-            // new_route[start] = new_route[*v];
-            // new_route[*v] = tmp;
-
-            let cost = Self::calculate_cost(&new_route, graph);
-
-            solutions.push(Self {
-                route: new_route,
-                cost,
-            });
-        }
-        solutions
+    /// Aplica um movimento já avaliado, atualizando a rota e o custo a partir da variação `delta`.
+    pub fn apply<T: Move>(&mut self, mv: &T, delta: f64) {
+        mv.apply(&mut self.route);
+        self.cost += delta;
     }
 
-    fn neighbourhood_by_shift(&self, graph: &[[f64; N]; N], start: usize) -> Vec<Self> {
-        let mut solutions: Vec<Solution<N>> = Vec::new();
-        let n = self.route.len();
-
-        if start >= n {
-            return solutions;
+    /// Variação de custo de `mv`, considerando o custo de trechos invertidos caso a matriz não
+    /// seja `symmetric`.
+    pub(crate) fn evaluate<M, T>(&self, graph: &M, symmetric: bool, mv: &T) -> f64
+    where
+        M: DistanceMatrix + ?Sized,
+        T: Move,
+    {
+        if symmetric {
+            mv.delta(&self.route, graph)
+        } else {
+            mv.asymmetric_delta(&self.route, graph)
         }
+    }
 
-        for target_pos in 0..n {
-            if target_pos == start {
-                continue;
-            }
-
-            let mut new_route = self.route.clone();
-            let elem = new_route.remove(start);
-            new_route.insert(target_pos, elem);
+    /// Retorna o movimento de maior melhora dentre `moves`, caso algum melhore a solução.
+    fn best_improvement<M, T>(
+        &self,
+        graph: &M,
+        symmetric: bool,
+        moves: impl Iterator<Item = T>,
+    ) -> Option<(T, f64)>
+    where
+        M: DistanceMatrix + ?Sized,
+        T: Move,
+    {
+        moves
+            .map(|mv| {
+                let delta = self.evaluate(graph, symmetric, &mv);
+                (mv, delta)
+            })
+            .fold(None, |best, (mv, delta)| match best {
+                Some((_, best_delta)) if best_delta <= delta => best,
+                _ if delta < -EPSILON => Some((mv, delta)),
+                _ => best,
+            })
+    }
 
-            let cost = Self::calculate_cost(&new_route, graph);
+    /// Retorna o primeiro movimento de `moves` que melhora a solução.
+    fn first_improvement<M, T>(
+        &self,
+        graph: &M,
+        symmetric: bool,
+        mut moves: impl Iterator<Item = T>,
+    ) -> Option<(T, f64)>
+    where
+        M: DistanceMatrix + ?Sized,
+        T: Move,
+    {
+        moves.find_map(|mv| {
+            let delta = self.evaluate(graph, symmetric, &mv);
+            (delta < -EPSILON).then_some((mv, delta))
+        })
+    }
 
-            solutions.push(Self {
-                route: new_route,
-                cost,
-            });
+    /// Aplica movimentos de uma vizinhança, gerada por `neighbourhood` a partir da solução
+    /// corrente, até que nenhum deles melhore a solução. Retorna o ótimo local e a quantidade de
+    /// movimentos aplicados.
+    fn descent<M, T, I>(
+        &self,
+        graph: &M,
+        pivot: Pivot,
+        neighbourhood: impl Fn(&Solution) -> I,
+    ) -> (Solution, usize)
+    where
+        M: DistanceMatrix + ?Sized,
+        T: Move,
+        I: Iterator<Item = T>,
+    {
+        let mut current_solution = self.clone();
+        let mut moves = 0;
+        let symmetric = graph.is_symmetric();
+
+        loop {
+            let improvement = match pivot {
+                Pivot::FirstImprovement => current_solution.first_improvement(
+                    graph,
+                    symmetric,
+                    neighbourhood(&current_solution),
+                ),
+                Pivot::BestImprovement => current_solution.best_improvement(
+                    graph,
+                    symmetric,
+                    neighbourhood(&current_solution),
+                ),
+            };
+
+            let Some((mv, delta)) = improvement else {
+                return (current_solution, moves);
+            };
+            current_solution.apply(&mv, delta);
+            moves += 1;
         }
+    }
 
-        solutions
+    fn neighbourhood_by_swap(&self, anchor: Option<usize>) -> impl Iterator<Item = Swap> + use<> {
+        let n = self.route.len();
+        let (first, last) = match anchor {
+            Some(p) if p < n => (p, p + 1),
+            Some(_) => (0, 0),
+            None => (0, n),
+        };
+
+        (first..last).flat_map(move |i| {
+            (0..n)
+                .filter(move |&j| (anchor.is_some() && j != i) || j > i)
+                .map(move |j| Swap { i, j })
+        })
     }
 
-    fn neighbourhood_by_or_opt(&self, graph: &[[f64; N]; N]) -> Vec<Self> {
+    fn neighbourhood_by_shift(&self, anchor: Option<usize>) -> impl Iterator<Item = OrOpt> + use<> {
         let n = self.route.len();
-        let mut neighbours = Vec::new();
+        let (first, last) = match anchor {
+            Some(p) if p < n => (p, p + 1),
+            Some(_) => (0, 0),
+            None => (0, n),
+        };
+
+        (first..last).flat_map(move |start| {
+            (0..n).filter(move |&to| to != start).map(move |to| OrOpt {
+                start,
+                len: 1,
+                to,
+                reversed: false,
+            })
+        })
+    }
 
-        for seq_len in 1..=3.min(n) {
-            for i in 0..n {
-                if i + seq_len > n {
-                    break;
-                }
+    fn neighbourhood_by_or_opt(&self, max_len: usize) -> impl Iterator<Item = OrOpt> + use<> {
+        let n = self.route.len();
 
-                for target in 0..n {
-                    if target >= i && target < i + seq_len {
-                        continue;
-                    }
+        (1..=max_len.min(n.saturating_sub(1))).flat_map(move |len| {
+            (0..=n - len).flat_map(move |start| {
+                (0..=n - len).flat_map(move |to| {
+                    [false, true]
+                        .into_iter()
+                        .filter(move |&reversed| if reversed { len > 1 } else { to != start })
+                        .map(move |reversed| OrOpt {
+                            start,
+                            len,
+                            to,
+                            reversed,
+                        })
+                })
+            })
+        })
+    }
 
-                    let mut new_route = self.route.clone();
-                    let sequence: Vec<usize> = new_route.drain(i..(i + seq_len)).collect();
+    fn neighbourhood_by_two_opt(&self) -> impl Iterator<Item = TwoOpt> + use<> {
+        let n = self.route.len();
 
-                    let insert_pos = if target > i { target - seq_len } else { target };
+        (0..n.saturating_sub(2)).flat_map(move |i| (i + 2..n).map(move |j| TwoOpt { i, j }))
+    }
 
-                    if insert_pos <= new_route.len() {
-                        new_route.splice(insert_pos..insert_pos, sequence);
+    /// Tripla de posições `(i, j, k)`, com `i < j < k`, das arestas removidas em um movimento
+    /// 3-opt.
+    fn three_opt_edges(&self) -> impl Iterator<Item = (usize, usize, usize)> + use<> {
+        let n = self.route.len();
 
-                        let cost = Self::calculate_cost(&new_route, graph);
+        (0..n).flat_map(move |i| (i + 1..n).flat_map(move |j| (j + 1..n).map(move |k| (i, j, k))))
+    }
 
-                        neighbours.push(Solution {
-                            route: new_route,
-                            cost,
-                        });
-                    }
-                }
-            }
-        }
+    fn neighbourhood_by_three_opt(&self) -> impl Iterator<Item = ThreeOpt> + use<> {
+        self.three_opt_edges()
+            .flat_map(|(i, j, k)| ThreeOpt::reconnections(i, j, k))
+    }
 
-        neighbours
+    fn neighbourhood_by_or3opt(&self) -> impl Iterator<Item = ThreeOpt> + use<> {
+        self.three_opt_edges().map(|(i, j, k)| ThreeOpt {
+            i,
+            j,
+            k,
+            exchange: true,
+            reverse_first: false,
+            reverse_second: false,
+        })
     }
 }
 
-impl<const N: usize> LocalSearch<[[f64; N]; N]> for Solution<N> {
-    fn swap(&self, graph: &[[f64; N]; N], start: usize) -> Self {
-        let mut best_solution: Solution<N> = self.clone();
-        let mut found_better_solution = true;
-
-        while found_better_solution {
-            found_better_solution = false;
-            let solutions = best_solution.neighbourhood_by_swap(graph, start);
-
-            let mut tmp_solution = best_solution.clone();
-
-            for s in solutions.iter() {
-                if s.cost < tmp_solution.cost {
-                    tmp_solution = s.clone();
-                }
-            }
-
-            if tmp_solution.cost < best_solution.cost {
-                best_solution = tmp_solution;
-                found_better_solution = true;
-            }
-        }
-
-        best_solution
+impl<M: DistanceMatrix + ?Sized> LocalSearch<M> for Solution {
+    fn swap(&self, graph: &M, anchor: Option<usize>) -> Self {
+        self.descent(graph, Pivot::BestImprovement, |s| {
+            s.neighbourhood_by_swap(anchor)
+        })
+        .0
     }
 
-    fn two_opt(&self, graph: &[[f64; N]; N]) -> Self {
-        let n = graph.len();
-        let mut current_solution: Solution<N> = self.clone();
+    fn two_opt(&self, graph: &M, pivot: Pivot) -> (Self, usize) {
+        self.descent(graph, pivot, Solution::neighbourhood_by_two_opt)
+    }
 
-        'outer: for i in 0..(n - 2) {
-            for j in i + 2..n {
-                let mut new_route: Vec<usize> = Vec::with_capacity(n);
+    fn shift(&self, graph: &M, anchor: Option<usize>) -> Self {
+        self.descent(graph, Pivot::BestImprovement, |s| {
+            s.neighbourhood_by_shift(anchor)
+        })
+        .0
+    }
 
-                new_route.extend_from_slice(&self.route[0..=i]);
-                new_route.extend(self.route[i + 1..=j].iter().rev());
+    fn or_opt(&self, graph: &M, max_len: usize) -> Self {
+        self.descent(graph, Pivot::BestImprovement, |s| {
+            s.neighbourhood_by_or_opt(max_len)
+        })
+        .0
+    }
 
-                if j + 1 < n {
-                    new_route.extend_from_slice(&self.route[j + 1..]);
-                }
+    // A vizinhança 3-opt é cúbica, então aplicamos o primeiro movimento de melhora encontrado.
+    fn three_opt(&self, graph: &M) -> Self {
+        self.descent(
+            graph,
+            Pivot::FirstImprovement,
+            Solution::neighbourhood_by_three_opt,
+        )
+        .0
+    }
 
-                let new_cost = Self::calculate_cost(&new_route, graph);
-                if new_cost < current_solution.cost {
-                    current_solution = Solution {
-                        route: new_route,
-                        cost: new_cost,
-                    };
-                    break 'outer;
-                }
-            }
-        }
-        current_solution
+    fn or3opt(&self, graph: &M) -> Self {
+        self.descent(
+            graph,
+            Pivot::FirstImprovement,
+            Solution::neighbourhood_by_or3opt,
+        )
+        .0
     }
 
-    fn shift(&self, graph: &[[f64; N]; N], start: usize) -> Self {
-        let mut best_solution: Solution<N> = self.clone();
+    fn lin_kernighan(&self, graph: &M, max_depth: usize) -> Self {
+        let mut best_solution = self.clone();
         let mut found_better_solution = true;
+        // Os ganhos da busca assumem que inverter um trecho não muda seu custo. Em matrizes
+        // assimétricas, cada melhora é conferida com o custo real da rota antes de ser aceita.
+        let symmetric = graph.is_symmetric();
 
         while found_better_solution {
             found_better_solution = false;
 
-            let solutions = best_solution.neighbourhood_by_shift(graph, start);
+            for t1 in self.route.iter().copied() {
+                for reversed in [false, true] {
+                    let previous_route = (!symmetric).then(|| best_solution.route.clone());
+                    let route = &mut best_solution.route;
+                    if reversed {
+                        route.reverse();
+                    }
+                    if let Some(p) = route.iter().position(|&v| v == t1) {
+                        route.rotate_left(p + 1);
+                    }
 
-            let mut tmp_solution = best_solution.clone();
+                    let gain = lin_kernighan::improve(route, graph, max_depth);
+                    let cost = match previous_route {
+                        None => best_solution.cost - gain,
+                        Some(_) => Solution::calculate_cost(route, graph),
+                    };
 
-            for s in solutions.iter() {
-                if s.cost < tmp_solution.cost {
-                    tmp_solution = s.clone();
+                    if cost < best_solution.cost - EPSILON {
+                        best_solution.cost = cost;
+                        found_better_solution = true;
+                    } else if let Some(previous_route) = previous_route {
+                        best_solution.route = previous_route;
+                    }
                 }
             }
-
-            if tmp_solution.cost < best_solution.cost {
-                best_solution = tmp_solution;
-                found_better_solution = true;
-            }
         }
+
         best_solution
     }
 
-    fn or_opt(&self, graph: &[[f64; N]; N]) -> Self {
-        let mut best_solution: Solution<N> = self.clone();
-        let mut found_better_solution = true;
-
-        while found_better_solution {
-            found_better_solution = false;
-
-            let solutions = best_solution.neighbourhood_by_or_opt(graph);
-
-            let mut tmp_solution = best_solution.clone();
-
-            for s in solutions.iter() {
-                if s.cost < tmp_solution.cost {
-                    tmp_solution = s.clone();
-                }
-            }
-
-            if tmp_solution.cost < best_solution.cost {
-                best_solution = tmp_solution;
-                found_better_solution = true;
-            }
+    fn two_opt_candidates(&self, graph: &M, candidates: Option<&Candidates>) -> Self {
+        match candidates {
+            Some(candidates) => self.two_opt_with_candidates(graph, candidates),
+            None => self.two_opt(graph, Pivot::FirstImprovement).0,
         }
+    }
 
-        best_solution
+    fn or_opt_candidates(
+        &self,
+        graph: &M,
+        max_len: usize,
+        candidates: Option<&Candidates>,
+    ) -> Self {
+        match candidates {
+            Some(candidates) => self.or_opt_with_candidates(graph, max_len, candidates),
+            None => self.or_opt(graph, max_len),
+        }
     }
 }
@@ -0,0 +1,215 @@
+//! Listas de candidatos e *don't-look bits* para buscas locais em instâncias maiores.
+//!
+//! Em vez de avaliar todos os pares de posições, as buscas só consideram movimentos que criam
+//! uma aresta entre um vértice e um dos seus `k` vizinhos mais próximos. Além disso, cada vértice
+//! tem um *don't-look bit*: após uma busca sem melhora a partir dele, o vértice só volta a ser
+//! explorado quando alguma aresta incidente a ele for alterada.
+
+use std::collections::VecDeque;
+
+use super::{
+    EPSILON, Solution,
+    moves::{Move, OrOpt, TwoOpt},
+};
+use crate::matrix::DistanceMatrix;
+
+/// Os `k` vizinhos mais próximos de cada vértice, do mais próximo para o mais distante.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Candidates {
+    lists: Vec<Vec<usize>>,
+}
+
+impl Candidates {
+    /// Calcula as listas de candidatos a partir da matriz de adjacência. Com `k` maior ou igual a
+    /// `n - 1`, todos os vértices são candidatos.
+    pub fn new<M: DistanceMatrix + ?Sized>(graph: &M, k: usize) -> Self {
+        let n = graph.node_count();
+        let lists = (0..n)
+            .map(|v| {
+                let mut others: Vec<usize> = (0..n).filter(|&u| u != v).collect();
+                others.sort_by(|&a, &b| graph.cost(v, a).total_cmp(&graph.cost(v, b)));
+                others.truncate(k);
+                others
+            })
+            .collect();
+
+        Self { lists }
+    }
+
+    /// Candidatos do vértice `v`.
+    pub fn of(&self, v: usize) -> &[usize] {
+        &self.lists[v]
+    }
+}
+
+/// Fila dos vértices cujo *don't-look bit* está desligado.
+struct DontLookBits {
+    queue: VecDeque<usize>,
+    active: Vec<bool>,
+}
+
+impl DontLookBits {
+    fn new(route: &[usize]) -> Self {
+        Self {
+            queue: route.iter().copied().collect(),
+            active: vec![true; route.len()],
+        }
+    }
+
+    fn pop(&mut self) -> Option<usize> {
+        let v = self.queue.pop_front()?;
+        self.active[v] = false;
+        Some(v)
+    }
+
+    fn reset(&mut self, nodes: impl IntoIterator<Item = usize>) {
+        for v in nodes {
+            if !self.active[v] {
+                self.active[v] = true;
+                self.queue.push_back(v);
+            }
+        }
+    }
+}
+
+/// Posição de cada vértice na rota.
+fn positions(route: &[usize]) -> Vec<usize> {
+    let mut pos = vec![0; route.len()];
+    for (i, &v) in route.iter().enumerate() {
+        pos[v] = i;
+    }
+    pos
+}
+
+impl Solution {
+    /// 2-opt guiado por listas de candidatos e *don't-look bits*, até um ótimo local.
+    ///
+    /// Para cada vértice `a` ativo e cada vizinho `b` de `a` na rota, tenta adicionar a aresta
+    /// `(a, c)` para os candidatos `c` de `a`. Como as listas estão ordenadas, a busca para no
+    /// primeiro candidato com `d(a, c) >= d(a, b)`, pois a partir dele nenhum movimento melhora
+    /// (em matrizes assimétricas, essa poda é apenas uma aproximação).
+    pub(super) fn two_opt_with_candidates<M: DistanceMatrix + ?Sized>(
+        &self,
+        graph: &M,
+        candidates: &Candidates,
+    ) -> Solution {
+        let mut solution = self.clone();
+        let n = solution.route.len();
+        if n < 4 {
+            return solution;
+        }
+
+        let mut pos = positions(&solution.route);
+        let mut dlb = DontLookBits::new(&solution.route);
+        let symmetric = graph.is_symmetric();
+
+        while let Some(a) = dlb.pop() {
+            'search: for forward in [true, false] {
+                let pa = pos[a];
+                // Posição da aresta removida incidente a `a` (e a `c`): `(p, p + 1)`.
+                let edge = |p: usize| if forward { p } else { (p + n - 1) % n };
+                let b = solution.route[if forward { (pa + 1) % n } else { edge(pa) }];
+
+                for &c in candidates.of(a) {
+                    if graph.cost(a, c) >= graph.cost(a, b) - EPSILON {
+                        break;
+                    }
+
+                    let (i, j) = (edge(pa).min(edge(pos[c])), edge(pa).max(edge(pos[c])));
+                    if j <= i + 1 || (i == 0 && j == n - 1) {
+                        continue;
+                    }
+
+                    let mv = TwoOpt { i, j };
+                    let delta = solution.evaluate(graph, symmetric, &mv);
+                    if delta < -EPSILON {
+                        let route = &solution.route;
+                        let touched = [route[i], route[i + 1], route[j], route[(j + 1) % n]];
+                        solution.apply(&mv, delta);
+                        pos = positions(&solution.route);
+                        dlb.reset(touched);
+                        break 'search;
+                    }
+                }
+            }
+        }
+
+        solution
+    }
+
+    /// Or-opt guiado por listas de candidatos e *don't-look bits*, até um ótimo local.
+    ///
+    /// Para cada vértice `a` ativo, considera as sequências de até `max_len` vértices que começam
+    /// em `a`, reinserindo-as (em ambas as orientações) imediatamente antes ou depois de algum
+    /// candidato de uma das extremidades da sequência.
+    pub(super) fn or_opt_with_candidates<M: DistanceMatrix + ?Sized>(
+        &self,
+        graph: &M,
+        max_len: usize,
+        candidates: &Candidates,
+    ) -> Solution {
+        let mut solution = self.clone();
+        let n = solution.route.len();
+        let mut pos = positions(&solution.route);
+        let mut dlb = DontLookBits::new(&solution.route);
+
+        while let Some(a) = dlb.pop() {
+            let start = pos[a];
+
+            'search: for len in 1..=max_len.min(n.saturating_sub(2)) {
+                if start + len > n {
+                    break;
+                }
+
+                let (s0, s1) = (solution.route[start], solution.route[start + len - 1]);
+                let ends = if len == 1 { vec![s0] } else { vec![s0, s1] };
+
+                for c in ends
+                    .into_iter()
+                    .flat_map(|e| candidates.of(e).iter().copied())
+                {
+                    let pc = pos[c];
+                    if (start..start + len).contains(&pc) {
+                        continue;
+                    }
+
+                    // Índice de `c` na rota sem a sequência.
+                    let rc = if pc < start { pc } else { pc - len };
+                    for to in [rc, rc + 1] {
+                        for reversed in [false, true] {
+                            if reversed && len == 1 {
+                                continue;
+                            }
+
+                            let mv = OrOpt {
+                                start,
+                                len,
+                                to,
+                                reversed,
+                            };
+                            let delta = mv.delta(&solution.route, graph);
+                            if delta < -EPSILON {
+                                let route = &solution.route;
+                                let touched = [
+                                    s0,
+                                    s1,
+                                    c,
+                                    route[(start + n - 1) % n],
+                                    route[(start + len) % n],
+                                    route[(pc + 1) % n],
+                                    route[(pc + n - 1) % n],
+                                ];
+                                solution.apply(&mv, delta);
+                                pos = positions(&solution.route);
+                                dlb.reset(touched);
+                                break 'search;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        solution
+    }
+}
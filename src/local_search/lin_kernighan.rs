@@ -0,0 +1,131 @@
+//! Busca local de profundidade variável no estilo Lin–Kernighan.
+//!
+//! Cada passo da busca é um movimento 2-opt sequencial: com a aresta `(t1, t2)` removida, a rota
+//! é um caminho de `t2` até `t1`, e a cada passo adicionamos uma aresta `(t2, t3)` e removemos
+//! `(t4, t3)`, onde `t4` antecede `t3` no caminho, invertendo o trecho entre `t2` e `t4`. O novo
+//! extremo livre do caminho é `t4`, e fechar o ciclo com `(t4, t1)` sempre gera uma rota válida.
+//! A sequência é aprofundada enquanto o ganho parcial for positivo, e ao fim é desfeita até o
+//! passo que gerou a melhor rota.
+
+use super::EPSILON;
+use crate::matrix::DistanceMatrix;
+
+/// Estado de uma sequência de movimentos a partir de um `t1` fixo no fim da rota.
+struct Chain {
+    /// Ganho parcial: custo das arestas removidas menos o das adicionadas.
+    gain: f64,
+    added: Vec<(usize, usize)>,
+    removed: Vec<(usize, usize)>,
+    /// Posição de `t3` em cada passo. Inverter `route[..p]` novamente desfaz o passo.
+    steps: Vec<usize>,
+}
+
+impl Chain {
+    /// Posições candidatas para `t3` no próximo passo, junto do ganho parcial após o passo.
+    fn candidates<M: DistanceMatrix + ?Sized>(
+        &self,
+        route: &[usize],
+        graph: &M,
+    ) -> Vec<(usize, f64)> {
+        let n = route.len();
+        let t2 = route[0];
+        let same_edge = |(a, b): (usize, usize), (u, v): (usize, usize)| {
+            (a == u && b == v) || (a == v && b == u)
+        };
+
+        (2..n - 1)
+            .filter_map(|p| {
+                let (t3, t4) = (route[p], route[p - 1]);
+                let partial = self.gain - graph.cost(t2, t3);
+                let allowed = partial > EPSILON
+                    && !self.removed.iter().any(|&e| same_edge(e, (t2, t3)))
+                    && !self.added.iter().any(|&e| same_edge(e, (t4, t3)));
+
+                allowed.then(|| (p, partial + graph.cost(t4, t3)))
+            })
+            .collect()
+    }
+
+    /// Aplica o passo com `t3` na posição `p` e retorna o ganho de fechar o ciclo após ele.
+    fn step<M: DistanceMatrix + ?Sized>(
+        &mut self,
+        route: &mut [usize],
+        graph: &M,
+        p: usize,
+        gain: f64,
+    ) -> f64 {
+        let (t1, t2, t3, t4) = (route[route.len() - 1], route[0], route[p], route[p - 1]);
+        route[..p].reverse();
+        self.added.push((t2, t3));
+        self.removed.push((t4, t3));
+        self.steps.push(p);
+        self.gain = gain;
+
+        gain - graph.cost(t4, t1)
+    }
+
+    /// Desfaz os passos a partir do passo `depth`.
+    fn undo(&mut self, route: &mut [usize], depth: usize) {
+        for p in self.steps.drain(depth..).rev() {
+            route[..p].reverse();
+        }
+        self.added.truncate(depth);
+        self.removed.truncate(depth + 1);
+    }
+}
+
+/// Executa sequências de movimentos Lin–Kernighan a partir do último vértice da rota (`t1`), com
+/// no máximo `max_depth` passos cada. No primeiro passo, todos os candidatos são tentados em
+/// ordem de ganho até que algum leve a uma melhora; nos seguintes, apenas o de maior ganho. A rota
+/// fica com o melhor prefixo da sequência aplicado, e o ganho (redução de custo) é retornado.
+pub(super) fn improve<M: DistanceMatrix + ?Sized>(
+    route: &mut [usize],
+    graph: &M,
+    max_depth: usize,
+) -> f64 {
+    let n = route.len();
+    if n < 5 || max_depth == 0 {
+        return 0.0;
+    }
+
+    let t1 = route[n - 1];
+    let mut chain = Chain {
+        gain: graph.cost(t1, route[0]),
+        added: Vec::with_capacity(max_depth),
+        removed: vec![(t1, route[0])],
+        steps: Vec::with_capacity(max_depth),
+    };
+    let initial_gain = chain.gain;
+
+    let mut first_steps = chain.candidates(route, graph);
+    first_steps.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    for (p, gain) in first_steps {
+        chain.gain = initial_gain;
+        let (mut best_gain, mut best_depth) = (chain.step(route, graph, p, gain), 1);
+
+        while chain.steps.len() < max_depth {
+            let Some((p, gain)) = chain
+                .candidates(route, graph)
+                .into_iter()
+                .max_by(|a, b| a.1.total_cmp(&b.1))
+            else {
+                break;
+            };
+
+            let closed = chain.step(route, graph, p, gain);
+            if closed > best_gain {
+                best_gain = closed;
+                best_depth = chain.steps.len();
+            }
+        }
+
+        if best_gain > EPSILON {
+            chain.undo(route, best_depth);
+            return best_gain;
+        }
+        chain.undo(route, 0);
+    }
+
+    0.0
+}
@@ -0,0 +1,366 @@
+//! Movimentos das vizinhanças das buscas locais.
+//!
+//! Cada movimento descreve uma alteração em uma rota cíclica (por posições, não por vértices) e
+//! sabe calcular a variação de custo que causaria olhando apenas para as poucas arestas afetadas,
+//! sem clonar a rota. A rota só é alterada, via [`Move::apply`], quando o movimento é aceito.
+
+use crate::matrix::DistanceMatrix;
+
+/// Um movimento de uma vizinhança de busca local.
+pub trait Move {
+    /// Variação no custo da rota caso o movimento seja aplicado. Valores negativos indicam que o
+    /// movimento melhora a solução.
+    fn delta<M: DistanceMatrix + ?Sized>(&self, route: &[usize], graph: &M) -> f64;
+
+    /// Aplica o movimento na rota.
+    fn apply(&self, route: &mut Vec<usize>);
+
+    /// Variação no custo da rota considerando também que, em matrizes assimétricas, trechos
+    /// percorridos no sentido contrário mudam de custo. Para movimentos que invertem trechos, custa
+    /// `O(tamanho dos trechos)` em vez de `O(1)`.
+    fn asymmetric_delta<M: DistanceMatrix + ?Sized>(&self, route: &[usize], graph: &M) -> f64 {
+        self.delta(route, graph)
+    }
+}
+
+/// Diferença entre o custo de percorrer `route[lo..=hi]` de trás para frente e o de percorrê-lo na
+/// ordem original.
+fn reversal_cost<M: DistanceMatrix + ?Sized>(
+    route: &[usize],
+    graph: &M,
+    lo: usize,
+    hi: usize,
+) -> f64 {
+    route[lo..=hi]
+        .windows(2)
+        .map(|w| graph.cost(w[1], w[0]) - graph.cost(w[0], w[1]))
+        .sum()
+}
+
+/// Troca os vértices das posições `i` e `j` da rota.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Swap {
+    pub i: usize,
+    pub j: usize,
+}
+
+impl Move for Swap {
+    fn delta<M: DistanceMatrix + ?Sized>(&self, route: &[usize], graph: &M) -> f64 {
+        let n = route.len();
+        let (i, j) = (self.i.min(self.j), self.i.max(self.j));
+        if i == j || n < 3 {
+            return 0.0;
+        }
+
+        let d = |u: usize, v: usize| graph.cost(route[u], route[v]);
+        let (pi, ni) = ((i + n - 1) % n, i + 1);
+        let (pj, nj) = (j - 1, (j + 1) % n);
+
+        if ni == j {
+            // `i` imediatamente antes de `j`: pi -> i -> j -> nj vira pi -> j -> i -> nj.
+            d(pi, j) + d(j, i) + d(i, nj) - d(pi, i) - d(i, j) - d(j, nj)
+        } else if nj == i {
+            // `j` é a última posição e `i` a primeira: pj -> j -> i -> ni vira pj -> i -> j -> ni.
+            d(pj, i) + d(i, j) + d(j, ni) - d(pj, j) - d(j, i) - d(i, ni)
+        } else {
+            d(pi, j) + d(j, ni) + d(pj, i) + d(i, nj) - d(pi, i) - d(i, ni) - d(pj, j) - d(j, nj)
+        }
+    }
+
+    fn apply(&self, route: &mut Vec<usize>) {
+        route.swap(self.i, self.j);
+    }
+}
+
+/// Remove a sequência de `len` vértices que começa na posição `start` e a reinsere na posição
+/// `to` da rota restante (i.e., sem a sequência), como em `Vec::remove` seguido de `Vec::insert`.
+/// Com `reversed`, a sequência é reinserida em ordem inversa.
+///
+/// Com `len = 1`, é o movimento da vizinhança *shift*.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OrOpt {
+    pub start: usize,
+    pub len: usize,
+    pub to: usize,
+    pub reversed: bool,
+}
+
+impl Move for OrOpt {
+    fn delta<M: DistanceMatrix + ?Sized>(&self, route: &[usize], graph: &M) -> f64 {
+        let n = route.len();
+        let OrOpt {
+            start,
+            len,
+            to,
+            reversed,
+        } = *self;
+        let m = n - len;
+        if (to == start && !reversed) || m == 0 {
+            return 0.0;
+        }
+
+        let d = |u: usize, v: usize| graph.cost(u, v);
+        let segment = &route[start..start + len];
+        let (s0, s1) = (segment[0], segment[len - 1]);
+        let (a, b) = (route[(start + n - 1) % n], route[(start + len) % n]);
+
+        // Índices da rota restante mapeados para a rota original.
+        let rest = |k: usize| if k < start { route[k] } else { route[k + len] };
+        let (u, v) = (rest((to + m - 1) % m), rest(to % m));
+
+        let removal = d(a, b) - d(a, s0) - d(s1, b);
+        if reversed {
+            // Em matrizes assimétricas, percorrer a sequência ao contrário muda seu custo interno.
+            let inner = reversal_cost(route, graph, start, start + len - 1);
+            removal + d(u, s1) + d(s0, v) - d(u, v) + inner
+        } else {
+            removal + d(u, s0) + d(s1, v) - d(u, v)
+        }
+    }
+
+    fn apply(&self, route: &mut Vec<usize>) {
+        let mut sequence: Vec<usize> = route.drain(self.start..self.start + self.len).collect();
+        if self.reversed {
+            sequence.reverse();
+        }
+        route.splice(self.to..self.to, sequence);
+    }
+}
+
+/// Inverte o trecho da rota entre as posições `i + 1` e `j` (inclusive), trocando as arestas
+/// `(i, i + 1)` e `(j, j + 1)` por `(i, j)` e `(i + 1, j + 1)`.
+///
+/// [`Move::delta`] considera apenas as duas arestas trocadas, assumindo que percorrer o trecho
+/// invertido custa o mesmo (matriz simétrica); [`Move::asymmetric_delta`] soma a diferença de custo
+/// do trecho invertido.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TwoOpt {
+    pub i: usize,
+    pub j: usize,
+}
+
+impl Move for TwoOpt {
+    fn delta<M: DistanceMatrix + ?Sized>(&self, route: &[usize], graph: &M) -> f64 {
+        let n = route.len();
+        let TwoOpt { i, j } = *self;
+        let d = |u: usize, v: usize| graph.cost(route[u], route[v]);
+
+        d(i, j) + d(i + 1, (j + 1) % n) - d(i, i + 1) - d(j, (j + 1) % n)
+    }
+
+    fn apply(&self, route: &mut Vec<usize>) {
+        route[self.i + 1..=self.j].reverse();
+    }
+
+    fn asymmetric_delta<M: DistanceMatrix + ?Sized>(&self, route: &[usize], graph: &M) -> f64 {
+        self.delta(route, graph) + reversal_cost(route, graph, self.i + 1, self.j)
+    }
+}
+
+/// Remove as arestas `(i, i + 1)`, `(j, j + 1)` e `(k, k + 1)` da rota e a reconecta. Sendo
+/// `B = [i + 1..=j]` e `C = [j + 1..=k]` os trechos entre as arestas removidas, o trecho
+/// `B C` é substituído por `B C` ou, com `exchange`, por `C B`, onde cada um dos dois trechos
+/// pode ser percorrido invertido (`reverse_first` e `reverse_second`).
+///
+/// Sem inversões e com `exchange`, é o movimento *or-3opt* (troca de trechos mantendo a
+/// orientação). Assim como no [`TwoOpt`], [`Move::delta`] assume que percorrer um trecho invertido
+/// custa o mesmo, enquanto [`Move::asymmetric_delta`] considera o custo dos trechos invertidos.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThreeOpt {
+    pub i: usize,
+    pub j: usize,
+    pub k: usize,
+    pub exchange: bool,
+    pub reverse_first: bool,
+    pub reverse_second: bool,
+}
+
+impl ThreeOpt {
+    /// Todas as 7 reconexões possíveis (exceto a identidade) das arestas `i`, `j` e `k`.
+    pub fn reconnections(i: usize, j: usize, k: usize) -> impl Iterator<Item = Self> {
+        (1..8).map(move |bits| ThreeOpt {
+            i,
+            j,
+            k,
+            exchange: bits & 1 != 0,
+            reverse_first: bits & 2 != 0,
+            reverse_second: bits & 4 != 0,
+        })
+    }
+
+    /// Trechos, na nova ordem, que ficam entre as posições `i` e `k + 1`, cada um como um par
+    /// `(início, fim)` de posições já considerando a orientação.
+    fn segments(&self) -> [(usize, usize); 2] {
+        let (b, c) = ((self.i + 1, self.j), (self.j + 1, self.k));
+        let (first, second) = if self.exchange { (c, b) } else { (b, c) };
+        let orient =
+            |(s, e): (usize, usize), reversed: bool| if reversed { (e, s) } else { (s, e) };
+
+        [
+            orient(first, self.reverse_first),
+            orient(second, self.reverse_second),
+        ]
+    }
+}
+
+impl Move for ThreeOpt {
+    fn delta<M: DistanceMatrix + ?Sized>(&self, route: &[usize], graph: &M) -> f64 {
+        let n = route.len();
+        let ThreeOpt { i, j, k, .. } = *self;
+        let d = |u: usize, v: usize| graph.cost(route[u], route[v]);
+        let [x, y] = self.segments();
+        let next = (k + 1) % n;
+
+        d(i, x.0) + d(x.1, y.0) + d(y.1, next) - d(i, i + 1) - d(j, j + 1) - d(k, next)
+    }
+
+    fn apply(&self, route: &mut Vec<usize>) {
+        let rearranged: Vec<usize> = self
+            .segments()
+            .into_iter()
+            .flat_map(|(s, e)| {
+                let (lo, hi) = (s.min(e), s.max(e));
+                let segment = route[lo..=hi].iter().copied();
+                if s > e {
+                    segment.rev().collect::<Vec<_>>()
+                } else {
+                    segment.collect()
+                }
+            })
+            .collect();
+        route[self.i + 1..=self.k].copy_from_slice(&rearranged);
+    }
+
+    fn asymmetric_delta<M: DistanceMatrix + ?Sized>(&self, route: &[usize], graph: &M) -> f64 {
+        let (b, c) = ((self.i + 1, self.j), (self.j + 1, self.k));
+        let (first, second) = if self.exchange { (c, b) } else { (b, c) };
+        let reversed = [(first, self.reverse_first), (second, self.reverse_second)];
+
+        self.delta(route, graph)
+            + reversed
+                .into_iter()
+                .filter(|&(_, reversed)| reversed)
+                .map(|((lo, hi), _)| reversal_cost(route, graph, lo, hi))
+                .sum::<f64>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{Rng, SeedableRng, rngs::StdRng, seq::SliceRandom};
+
+    use super::*;
+    use crate::{local_search::Solution, matrix::Matrix};
+
+    /// Tamanhos de rota testados, incluindo os casos pequenos em que os trechos dão a volta na
+    /// rota.
+    const SIZES: std::ops::RangeInclusive<usize> = 3..=9;
+
+    /// Matriz aleatória com diagonal nula, simétrica ou não.
+    fn random_matrix(rng: &mut StdRng, n: usize, symmetric: bool) -> Matrix {
+        let mut m = Matrix::new(n);
+        for u in 0..n {
+            for v in 0..n {
+                if u != v && (!symmetric || u < v) {
+                    m[u][v] = rng.random_range(1.0..100.0);
+                    if symmetric {
+                        m[v][u] = m[u][v];
+                    }
+                }
+            }
+        }
+        m
+    }
+
+    /// Verifica, para cada movimento, que `delta` é a diferença real de custo da rota após
+    /// aplicá-lo.
+    fn check<T: Move + std::fmt::Debug>(
+        graph: &Matrix,
+        route: &[usize],
+        moves: impl IntoIterator<Item = T>,
+        delta: impl Fn(&T, &[usize], &Matrix) -> f64,
+    ) {
+        let before = Solution::calculate_cost(route, graph);
+        for mv in moves {
+            let mut after = route.to_vec();
+            mv.apply(&mut after);
+            let expected = Solution::calculate_cost(&after, graph) - before;
+            let got = delta(&mv, route, graph);
+            assert!(
+                (got - expected).abs() < 1e-9,
+                "{mv:?} on {route:?}: delta {got}, expected {expected}"
+            );
+        }
+    }
+
+    fn swaps(n: usize) -> impl Iterator<Item = Swap> {
+        (0..n).flat_map(move |i| (0..n).map(move |j| Swap { i, j }))
+    }
+
+    fn or_opts(n: usize) -> impl Iterator<Item = OrOpt> {
+        (1..n).flat_map(move |len| {
+            (0..=n - len).flat_map(move |start| {
+                (0..=n - len).flat_map(move |to| {
+                    [false, true].map(|reversed| OrOpt {
+                        start,
+                        len,
+                        to,
+                        reversed,
+                    })
+                })
+            })
+        })
+    }
+
+    fn two_opts(n: usize) -> impl Iterator<Item = TwoOpt> {
+        (0..n).flat_map(move |i| (i + 1..n).map(move |j| TwoOpt { i, j }))
+    }
+
+    fn three_opts(n: usize) -> impl Iterator<Item = ThreeOpt> {
+        (0..n).flat_map(move |i| {
+            (i + 1..n)
+                .flat_map(move |j| (j + 1..n).flat_map(move |k| ThreeOpt::reconnections(i, j, k)))
+        })
+    }
+
+    /// Executa `test` sobre algumas rotas aleatórias de cada tamanho em [`SIZES`].
+    fn for_each_instance(symmetric: bool, test: impl Fn(&Matrix, &[usize])) {
+        let mut rng = StdRng::seed_from_u64(0);
+        for n in SIZES {
+            for _ in 0..5 {
+                let graph = random_matrix(&mut rng, n, symmetric);
+                let mut route: Vec<usize> = (0..n).collect();
+                route.shuffle(&mut rng);
+                test(&graph, &route);
+            }
+        }
+    }
+
+    #[test]
+    fn symmetric_delta_matches_cost_difference() {
+        for_each_instance(true, |graph, route| {
+            let n = route.len();
+            check(graph, route, swaps(n), |mv, r, g| mv.delta(r, g));
+            check(graph, route, or_opts(n), |mv, r, g| mv.delta(r, g));
+            check(graph, route, two_opts(n), |mv, r, g| mv.delta(r, g));
+            check(graph, route, three_opts(n), |mv, r, g| mv.delta(r, g));
+        });
+    }
+
+    #[test]
+    fn asymmetric_delta_matches_cost_difference() {
+        for_each_instance(false, |graph, route| {
+            let n = route.len();
+            check(graph, route, swaps(n), |mv, r, g| mv.asymmetric_delta(r, g));
+            check(graph, route, or_opts(n), |mv, r, g| {
+                mv.asymmetric_delta(r, g)
+            });
+            check(graph, route, two_opts(n), |mv, r, g| {
+                mv.asymmetric_delta(r, g)
+            });
+            check(graph, route, three_opts(n), |mv, r, g| {
+                mv.asymmetric_delta(r, g)
+            });
+        });
+    }
+}
@@ -0,0 +1,229 @@
+//! Descida em Vizinhança Variável (*Variable Neighbourhood Descent*, VND) sobre as buscas locais
+//! de [`LocalSearch`].
+
+use std::{borrow::Cow, fmt, str::FromStr};
+
+use super::{EPSILON, LocalSearch, Pivot, Solution, candidates::Candidates};
+use crate::matrix::DistanceMatrix;
+
+/// Uma das vizinhanças de [`LocalSearch`], usada para montar sequências de buscas locais.
+///
+/// Pode ser lida a partir dos nomes `swap`, `shift`, `2opt`, `2opt-best`, `2opt-nn`, `oropt`,
+/// `oropt-nn`, `3opt`, `or3opt` e `lk`. As vizinhanças restritas aceitam a quantidade de vizinhos
+/// mais próximos, e.g. `2opt-nn:8` (por padrão, 10), e o or-opt aceita o tamanho máximo das
+/// sequências (por padrão, 3), e.g. `oropt:2` ou, junto dos vizinhos, `oropt-nn:8:2`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Neighbourhood {
+    /// Troca de todos os pares de posições.
+    Swap,
+    /// Realocação de um único vértice, de qualquer posição para qualquer outra.
+    Shift,
+    /// 2-opt com a regra de pivoteamento dada.
+    TwoOpt(Pivot),
+    /// Or-opt com sequências de até o tamanho dado.
+    OrOpt(usize),
+    ThreeOpt,
+    Or3Opt,
+    /// Lin–Kernighan com a profundidade máxima dada.
+    LinKernighan(usize),
+    /// 2-opt restrito aos `k` vizinhos mais próximos de cada vértice, com *don't-look bits*.
+    TwoOptNeighbours {
+        k: usize,
+    },
+    /// Or-opt restrito aos `k` vizinhos mais próximos de cada vértice, com *don't-look bits*.
+    OrOptNeighbours {
+        max_len: usize,
+        k: usize,
+    },
+}
+
+impl Neighbourhood {
+    /// Executa a busca local desta vizinhança até um ótimo local.
+    ///
+    /// As vizinhanças restritas usam as listas de `candidates`; caso elas não tenham sido
+    /// calculadas para o `k` da vizinhança, são calculadas apenas para esta busca.
+    pub fn descend<M: DistanceMatrix + ?Sized>(
+        self,
+        solution: &Solution,
+        graph: &M,
+        candidates: &CandidateLists,
+    ) -> Solution {
+        let lists = |k: usize| {
+            candidates
+                .get(k)
+                .map_or_else(|| Cow::Owned(Candidates::new(graph, k)), Cow::Borrowed)
+        };
+
+        match self {
+            Neighbourhood::Swap => solution.swap(graph, None),
+            Neighbourhood::Shift => solution.shift(graph, None),
+            Neighbourhood::TwoOpt(pivot) => solution.two_opt(graph, pivot).0,
+            Neighbourhood::OrOpt(max_len) => solution.or_opt(graph, max_len),
+            Neighbourhood::ThreeOpt => solution.three_opt(graph),
+            Neighbourhood::Or3Opt => solution.or3opt(graph),
+            Neighbourhood::LinKernighan(max_depth) => solution.lin_kernighan(graph, max_depth),
+            Neighbourhood::TwoOptNeighbours { k } => {
+                solution.two_opt_candidates(graph, Some(&lists(k)))
+            }
+            Neighbourhood::OrOptNeighbours { max_len, k } => {
+                solution.or_opt_candidates(graph, max_len, Some(&lists(k)))
+            }
+        }
+    }
+
+    /// Quantidade de vizinhos mais próximos usada pela vizinhança, caso ela seja restrita a
+    /// listas de candidatos.
+    pub fn candidates(&self) -> Option<usize> {
+        match *self {
+            Neighbourhood::TwoOptNeighbours { k } | Neighbourhood::OrOptNeighbours { k, .. } => {
+                Some(k)
+            }
+            _ => None,
+        }
+    }
+
+    /// Lê uma lista de vizinhanças separadas por vírgula, e.g. `swap,2opt,oropt`.
+    pub fn parse_list(s: &str) -> Result<Vec<Self>, String> {
+        s.split(',').map(str::parse).collect()
+    }
+}
+
+/// Quantidade de vizinhos mais próximos das vizinhanças restritas quando ela não é informada.
+const DEFAULT_NEIGHBOURS: usize = 10;
+
+/// Tamanho máximo das sequências movidas pelo or-opt quando ele não é informado.
+const DEFAULT_SEGMENT_LENGTH: usize = 3;
+
+impl FromStr for Neighbourhood {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let mut parts = s.split(':');
+        let name = parts.next().unwrap_or_default();
+        let params = parts
+            .map(|p| {
+                p.parse()
+                    .map_err(|_| format!("invalid parameter {p:?} in {s}"))
+            })
+            .collect::<Result<Vec<usize>, _>>()?;
+        if params.contains(&0) {
+            return Err(format!("parameters must be positive: {s}"));
+        }
+
+        match (name, params.as_slice()) {
+            ("2opt-nn", &[]) => Ok(Neighbourhood::TwoOptNeighbours {
+                k: DEFAULT_NEIGHBOURS,
+            }),
+            ("2opt-nn", &[k]) => Ok(Neighbourhood::TwoOptNeighbours { k }),
+            ("oropt", &[]) => Ok(Neighbourhood::OrOpt(DEFAULT_SEGMENT_LENGTH)),
+            ("oropt", &[max_len]) => Ok(Neighbourhood::OrOpt(max_len)),
+            ("oropt-nn", &[]) => Ok(Neighbourhood::OrOptNeighbours {
+                max_len: DEFAULT_SEGMENT_LENGTH,
+                k: DEFAULT_NEIGHBOURS,
+            }),
+            ("oropt-nn", &[k]) => Ok(Neighbourhood::OrOptNeighbours {
+                max_len: DEFAULT_SEGMENT_LENGTH,
+                k,
+            }),
+            ("oropt-nn", &[k, max_len]) => Ok(Neighbourhood::OrOptNeighbours { max_len, k }),
+            ("swap", &[]) => Ok(Neighbourhood::Swap),
+            ("shift", &[]) => Ok(Neighbourhood::Shift),
+            ("2opt", &[]) => Ok(Neighbourhood::TwoOpt(Pivot::FirstImprovement)),
+            ("2opt-best", &[]) => Ok(Neighbourhood::TwoOpt(Pivot::BestImprovement)),
+            ("3opt", &[]) => Ok(Neighbourhood::ThreeOpt),
+            ("or3opt", &[]) => Ok(Neighbourhood::Or3Opt),
+            ("lk", &[]) => Ok(Neighbourhood::LinKernighan(5)),
+            _ => Err(format!("unknown neighbourhood: {s}")),
+        }
+    }
+}
+
+impl fmt::Display for Neighbourhood {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Neighbourhood::Swap => write!(f, "swap"),
+            Neighbourhood::Shift => write!(f, "shift"),
+            Neighbourhood::TwoOpt(Pivot::FirstImprovement) => write!(f, "2-opt"),
+            Neighbourhood::TwoOpt(Pivot::BestImprovement) => write!(f, "2-opt (best improvement)"),
+            Neighbourhood::OrOpt(max_len) => write!(f, "or-opt ({max_len})"),
+            Neighbourhood::ThreeOpt => write!(f, "3-opt"),
+            Neighbourhood::Or3Opt => write!(f, "or-3opt"),
+            Neighbourhood::LinKernighan(max_depth) => write!(f, "lin-kernighan ({max_depth})"),
+            Neighbourhood::TwoOptNeighbours { k } => write!(f, "2-opt ({k} nearest)"),
+            Neighbourhood::OrOptNeighbours { max_len, k } => {
+                write!(f, "or-opt ({max_len}, {k} nearest)")
+            }
+        }
+    }
+}
+
+/// Listas de candidatos das vizinhanças restritas (`2opt-nn` e `oropt-nn`) de uma sequência de
+/// vizinhanças. São calculadas uma única vez por grafo, para cada `k` usado, e reaproveitadas por
+/// todas as buscas locais.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CandidateLists {
+    lists: Vec<(usize, Candidates)>,
+}
+
+impl CandidateLists {
+    /// Calcula as listas de candidatos de cada `k` usado pelas vizinhanças de `neighbourhoods`.
+    pub fn new<M: DistanceMatrix + ?Sized>(graph: &M, neighbourhoods: &[Neighbourhood]) -> Self {
+        let mut lists: Vec<(usize, Candidates)> = Vec::new();
+        for k in neighbourhoods.iter().filter_map(Neighbourhood::candidates) {
+            if lists.iter().all(|&(other, _)| other != k) {
+                lists.push((k, Candidates::new(graph, k)));
+            }
+        }
+
+        Self { lists }
+    }
+
+    /// Listas com os `k` vizinhos mais próximos de cada vértice, caso tenham sido calculadas.
+    pub fn get(&self, k: usize) -> Option<&Candidates> {
+        self.lists
+            .iter()
+            .find(|&&(other, _)| other == k)
+            .map(|(_, candidates)| candidates)
+    }
+}
+
+/// Descida em Vizinhança Variável.
+///
+/// # Argumentos
+/// - `solution`: solução inicial;
+/// - `graph`: grafo representado em matriz de adjacência;
+/// - `neighbourhoods`: vizinhanças na ordem em que serão exploradas;
+/// - `candidates`: listas de candidatos das vizinhanças restritas, ver [`CandidateLists::new`].
+///
+/// # Saída
+/// Uma solução que é ótimo local em relação a todas as vizinhanças de `neighbourhoods`.
+///
+/// # Detalhes de funcionamento
+/// 1. Começa pela primeira vizinhança da lista;
+/// 2. Executa a busca local da vizinhança corrente até um ótimo local;
+/// 3. Se a solução melhorou, volta para a primeira vizinhança; caso contrário, avança para a
+///    próxima;
+/// 4. Termina quando nenhuma das vizinhanças melhora a solução.
+pub fn vnd<M: DistanceMatrix + ?Sized>(
+    solution: &Solution,
+    graph: &M,
+    neighbourhoods: &[Neighbourhood],
+    candidates: &CandidateLists,
+) -> Solution {
+    let mut best_solution = solution.clone();
+    let mut k = 0;
+
+    while k < neighbourhoods.len() {
+        let candidate = neighbourhoods[k].descend(&best_solution, graph, candidates);
+
+        if candidate.cost < best_solution.cost - EPSILON {
+            best_solution = candidate;
+            k = 0;
+        } else {
+            k += 1;
+        }
+    }
+
+    best_solution
+}
@@ -0,0 +1,110 @@
+use std::{
+    ops::{Index, IndexMut},
+    slice::ChunksExact,
+};
+
+/// Matriz de adjacência quadrada com tamanho definido em tempo de execução.
+///
+/// Os pesos são guardados de forma contígua, linha a linha, e `matrix[i][j]` devolve o custo da
+/// aresta `(i, j)`, assim como acontece com as matrizes `[[f64; N]; N]` geradas por
+/// `graph_from_csv!`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Matrix {
+    n: usize,
+    data: Vec<f64>,
+}
+
+impl Matrix {
+    /// Cria uma matriz `n x n` com todos os pesos iguais a zero.
+    pub fn new(n: usize) -> Self {
+        Self {
+            n,
+            data: vec![0.0; n * n],
+        }
+    }
+
+    /// Cria uma matriz a partir de suas linhas. Retorna `None` caso as linhas não formem uma
+    /// matriz quadrada.
+    pub fn from_rows(rows: Vec<Vec<f64>>) -> Option<Self> {
+        let n = rows.len();
+        if rows.iter().any(|r| r.len() != n) {
+            return None;
+        }
+
+        Some(Self {
+            n,
+            data: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Quantidade de vértices (linhas) da matriz.
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Itera sobre as linhas da matriz.
+    pub fn iter(&self) -> ChunksExact<'_, f64> {
+        self.data.chunks_exact(self.n.max(1))
+    }
+}
+
+impl Index<usize> for Matrix {
+    type Output = [f64];
+
+    fn index(&self, i: usize) -> &[f64] {
+        &self.data[i * self.n..(i + 1) * self.n]
+    }
+}
+
+impl IndexMut<usize> for Matrix {
+    fn index_mut(&mut self, i: usize) -> &mut [f64] {
+        &mut self.data[i * self.n..(i + 1) * self.n]
+    }
+}
+
+/// Abstração sobre matrizes de adjacência completas, usada pelas buscas locais para que funcionem
+/// tanto com a [`Matrix`] carregada em tempo de execução quanto com as matrizes `[[f64; N]; N]`
+/// geradas por `graph_from_csv!`.
+pub trait DistanceMatrix {
+    /// Quantidade de vértices do grafo.
+    fn node_count(&self) -> usize;
+
+    /// Custo da aresta que sai de `from` e chega em `to`.
+    fn cost(&self, from: usize, to: usize) -> f64;
+
+    /// Verifica se a matriz é simétrica, i.e., se `cost(u, v) == cost(v, u)` para todo par de
+    /// vértices. Em matrizes assimétricas (e.g. tempos de viagem), percorrer um trecho da rota no
+    /// sentido contrário muda o seu custo.
+    fn is_symmetric(&self) -> bool {
+        let n = self.node_count();
+        (0..n).all(|u| (u + 1..n).all(|v| self.cost(u, v) == self.cost(v, u)))
+    }
+}
+
+impl DistanceMatrix for Matrix {
+    #[inline]
+    fn node_count(&self) -> usize {
+        self.n
+    }
+
+    #[inline]
+    fn cost(&self, from: usize, to: usize) -> f64 {
+        self.data[from * self.n + to]
+    }
+}
+
+impl<const N: usize> DistanceMatrix for [[f64; N]; N] {
+    #[inline]
+    fn node_count(&self) -> usize {
+        N
+    }
+
+    #[inline]
+    fn cost(&self, from: usize, to: usize) -> f64 {
+        self[from][to]
+    }
+}
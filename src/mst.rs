@@ -0,0 +1,233 @@
+//! Árvores geradoras mínimas e as heurísticas de aproximação baseadas nelas.
+//!
+//! Todas as funções assumem uma matriz simétrica. As garantias de aproximação da árvore dupla
+//! (2) e de Christofides (3/2) só valem quando a matriz também respeita a desigualdade triangular,
+//! o que pode ser verificado com [`triangle_inequality_violation`].
+
+use crate::{disjoint_set::DisjointSet, local_search::Solution, matrix::DistanceMatrix};
+
+mod matching;
+
+use matching::minimum_matching;
+
+/// Uma árvore geradora, dada pelas suas arestas.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpanningTree {
+    pub edges: Vec<(usize, usize)>,
+    pub cost: f64,
+}
+
+impl SpanningTree {
+    /// Lista de adjacência da árvore.
+    pub fn adjacency(&self, n: usize) -> Vec<Vec<usize>> {
+        let mut adjacency = vec![Vec::new(); n];
+        for &(u, v) in &self.edges {
+            adjacency[u].push(v);
+            adjacency[v].push(u);
+        }
+        adjacency
+    }
+}
+
+/// Algoritmo de Prim em O(n²), adequado para grafos completos.
+///
+/// # Detalhes de funcionamento
+/// 1. A árvore começa apenas com o vértice `0`;
+/// 2. O vetor `best` guarda, para cada vértice fora da árvore, a aresta mais barata que o liga a
+///    ela;
+/// 3. A cada passo, o vértice fora da árvore com a aresta mais barata é adicionado, e `best` é
+///    atualizado em O(n) com as arestas do novo vértice.
+pub fn prim<M: DistanceMatrix + ?Sized>(graph: &M) -> SpanningTree {
+    prim_without(graph, None)
+}
+
+/// Algoritmo de Prim sobre todos os vértices exceto `excluded`.
+fn prim_without<M: DistanceMatrix + ?Sized>(graph: &M, excluded: Option<usize>) -> SpanningTree {
+    let n = graph.node_count();
+    let mut in_tree = vec![false; n];
+    let mut tree = SpanningTree {
+        edges: Vec::with_capacity(n.saturating_sub(1)),
+        cost: 0.0,
+    };
+    let Some(root) = (0..n).find(|&v| Some(v) != excluded) else {
+        return tree;
+    };
+    let mut best: Vec<(f64, usize)> = (0..n).map(|v| (graph.cost(root, v), root)).collect();
+    in_tree[root] = true;
+    if let Some(excluded) = excluded {
+        in_tree[excluded] = true;
+    }
+
+    for _ in 1 + usize::from(excluded.is_some())..n {
+        let v = (0..n)
+            .filter(|&v| !in_tree[v])
+            .min_by(|&a, &b| best[a].0.total_cmp(&best[b].0))
+            .expect("No vertex left outside the tree");
+
+        in_tree[v] = true;
+        tree.edges.push((best[v].1, v));
+        tree.cost += best[v].0;
+
+        for u in 0..n {
+            if !in_tree[u] && graph.cost(v, u) < best[u].0 {
+                best[u] = (graph.cost(v, u), v);
+            }
+        }
+    }
+
+    tree
+}
+
+/// 1-tree mínima com vértice especial `special`: uma árvore geradora mínima dos demais vértices
+/// mais as duas arestas mais baratas que saem de `special`. Toda rota é uma 1-tree, logo o custo
+/// da 1-tree mínima é um limite inferior para o custo da rota ótima.
+pub fn one_tree<M: DistanceMatrix + ?Sized>(graph: &M, special: usize) -> SpanningTree {
+    let n = graph.node_count();
+    let mut tree = prim_without(graph, Some(special));
+
+    let mut neighbours: Vec<usize> = (0..n).filter(|&v| v != special).collect();
+    neighbours.sort_by(|&u, &v| graph.cost(special, u).total_cmp(&graph.cost(special, v)));
+    for &v in neighbours.iter().take(2) {
+        tree.edges.push((special, v));
+        tree.cost += graph.cost(special, v);
+    }
+
+    tree
+}
+
+/// Algoritmo de Kruskal: percorre as arestas em ordem crescente de custo, adicionando as que não
+/// fecham ciclo, verificado com conjuntos disjuntos.
+pub fn kruskal<M: DistanceMatrix + ?Sized>(graph: &M) -> SpanningTree {
+    let n = graph.node_count();
+    let mut edges: Vec<(usize, usize)> = (0..n)
+        .flat_map(|u| (u + 1..n).map(move |v| (u, v)))
+        .collect();
+    edges.sort_by(|&(a, b), &(u, v)| graph.cost(a, b).total_cmp(&graph.cost(u, v)));
+
+    let mut components = DisjointSet::new(n);
+    let mut tree = SpanningTree {
+        edges: Vec::with_capacity(n.saturating_sub(1)),
+        cost: 0.0,
+    };
+
+    for (u, v) in edges {
+        if tree.edges.len() + 1 >= n {
+            break;
+        }
+        if components.union(u, v) {
+            tree.edges.push((u, v));
+            tree.cost += graph.cost(u, v);
+        }
+    }
+
+    tree
+}
+
+/// Procura uma tripla `(i, j, k)` que viole a desigualdade triangular, i.e., com
+/// `d(i, j) > d(i, k) + d(k, j)`. Retorna `None` caso a matriz a respeite.
+pub fn triangle_inequality_violation<M: DistanceMatrix + ?Sized>(
+    graph: &M,
+) -> Option<(usize, usize, usize)> {
+    let n = graph.node_count();
+
+    (0..n)
+        .flat_map(|i| (0..n).flat_map(move |j| (0..n).map(move |k| (i, j, k))))
+        .find(|&(i, j, k)| graph.cost(i, j) > graph.cost(i, k) + graph.cost(k, j) + 1e-9)
+}
+
+/// Remove as repetições de vértices de um circuito, mantendo a primeira ocorrência de cada um
+/// (*shortcutting*).
+fn shortcut<M: DistanceMatrix + ?Sized>(
+    graph: &M,
+    circuit: impl IntoIterator<Item = usize>,
+) -> Solution {
+    let mut visited = vec![false; graph.node_count()];
+    let route: Vec<usize> = circuit
+        .into_iter()
+        .filter(|&v| !std::mem::replace(&mut visited[v], true))
+        .collect();
+    let cost = Solution::calculate_cost(&route, graph);
+
+    Solution { route, cost }
+}
+
+/// Heurística da árvore dupla (*double tree*), uma 2-aproximação para instâncias métricas.
+///
+/// # Argumentos
+/// - `graph`: grafo representado em matriz de adjacência;
+/// - `start`: vértice por onde o ciclo começa.
+///
+/// # Detalhes de funcionamento
+/// 1. Calcula uma árvore geradora mínima com [`prim`];
+/// 2. Duplica as arestas da árvore, obtendo um multigrafo euleriano cujo circuito é o percurso em
+///    profundidade da árvore a partir de `start`;
+/// 3. Remove as repetições de vértices do circuito, o que equivale à ordem de pré-ordem da DFS.
+pub fn double_tree<M: DistanceMatrix + ?Sized>(graph: &M, start: usize) -> Solution {
+    let n = graph.node_count();
+    let adjacency = prim(graph).adjacency(n);
+
+    let mut preorder = Vec::with_capacity(n);
+    let mut visited = vec![false; n];
+    let mut stack = vec![start];
+    while let Some(v) = stack.pop() {
+        if std::mem::replace(&mut visited[v], true) {
+            continue;
+        }
+        preorder.push(v);
+        stack.extend(adjacency[v].iter().rev().filter(|&&u| !visited[u]));
+    }
+
+    shortcut(graph, preorder)
+}
+
+/// Circuito euleriano de um multigrafo conexo com todos os graus pares, pelo algoritmo de
+/// Hierholzer.
+fn eulerian_circuit(n: usize, edges: &[(usize, usize)], start: usize) -> Vec<usize> {
+    let mut adjacency: Vec<Vec<(usize, usize)>> = vec![Vec::new(); n];
+    for (id, &(u, v)) in edges.iter().enumerate() {
+        adjacency[u].push((v, id));
+        adjacency[v].push((u, id));
+    }
+
+    let mut used = vec![false; edges.len()];
+    let mut circuit = Vec::with_capacity(edges.len() + 1);
+    let mut stack = vec![start];
+    while let Some(&v) = stack.last() {
+        match adjacency[v].pop() {
+            Some((_, id)) if used[id] => {}
+            Some((u, id)) => {
+                used[id] = true;
+                stack.push(u);
+            }
+            None => circuit.extend(stack.pop()),
+        }
+    }
+
+    circuit.reverse();
+    circuit
+}
+
+/// Algoritmo de Christofides, uma 3/2-aproximação para instâncias métricas.
+///
+/// # Argumentos
+/// - `graph`: grafo representado em matriz de adjacência;
+/// - `start`: vértice por onde o ciclo começa.
+///
+/// # Detalhes de funcionamento
+/// 1. Calcula uma árvore geradora mínima com [`prim`];
+/// 2. Encontra os vértices de grau ímpar na árvore (sempre em quantidade par) e um emparelhamento
+///    perfeito de custo mínimo entre eles, pelo algoritmo de *blossom* de Edmonds em O(k³);
+/// 3. A união da árvore com o emparelhamento é um multigrafo com todos os graus pares, do qual se
+///    extrai um circuito euleriano a partir de `start`;
+/// 4. Remove as repetições de vértices do circuito.
+pub fn christofides<M: DistanceMatrix + ?Sized>(graph: &M, start: usize) -> Solution {
+    let n = graph.node_count();
+    let tree = prim(graph);
+    let adjacency = tree.adjacency(n);
+
+    let odd: Vec<usize> = (0..n).filter(|&v| adjacency[v].len() % 2 == 1).collect();
+    let mut edges = tree.edges;
+    edges.extend(minimum_matching(graph, &odd));
+
+    shortcut(graph, eulerian_circuit(n, &edges, start))
+}
@@ -0,0 +1,623 @@
+//! Emparelhamento perfeito de custo mínimo em grafos completos, pelo algoritmo de *blossom* de
+//! Edmonds em O(k³).
+//!
+//! A implementação segue a versão com pesos e variáveis duais do algoritmo (Galil, "Efficient
+//! algorithms for finding maximum matching in graphs", 1986), que encontra um emparelhamento de
+//! cardinalidade máxima e, dentre eles, de peso máximo. Em um grafo completo com quantidade par de
+//! vértices, todo emparelhamento de cardinalidade máxima é perfeito; com pesos `W − custo`, o de
+//! peso máximo é o de custo mínimo.
+
+use crate::matrix::DistanceMatrix;
+
+/// Marca a ausência de vértice, aresta ou *blossom*.
+const NONE: usize = usize::MAX;
+
+/// Os custos são arredondados para múltiplos de `1 / SCALE` e tratados como inteiros, para que as
+/// comparações de folga do algoritmo sejam exatas.
+const SCALE: f64 = 1e6;
+
+/// Emparelhamento perfeito de custo mínimo entre os vértices de `nodes` (em quantidade par).
+pub(super) fn minimum_matching<M: DistanceMatrix + ?Sized>(
+    graph: &M,
+    nodes: &[usize],
+) -> Vec<(usize, usize)> {
+    let k = nodes.len();
+    let cost = |a: usize, b: usize| (graph.cost(nodes[a], nodes[b]) * SCALE).round() as i64;
+    let max_cost = (0..k)
+        .flat_map(|a| (a + 1..k).map(move |b| (a, b)))
+        .map(|(a, b)| cost(a, b))
+        .max()
+        .unwrap_or(0);
+
+    // Pesos pares mantêm todas as variáveis duais inteiras.
+    let edges = (0..k)
+        .flat_map(|a| (a + 1..k).map(move |b| (a, b)))
+        .map(|(a, b)| (a, b, 2 * (max_cost + 1 - cost(a, b))))
+        .collect();
+    let mate = Blossom::new(k, edges).solve();
+
+    (0..k)
+        .filter(|&a| mate[a] != NONE && mate[a] > a)
+        .map(|a| (nodes[a], nodes[mate[a]]))
+        .collect()
+}
+
+/// Estado do algoritmo de *blossom*.
+///
+/// Os vértices são `0..n` e os *blossoms* não triviais, `n..2n`. Cada aresta `k` tem duas
+/// extremidades, `2k` e `2k + 1`, de forma que `p ^ 1` é a extremidade oposta a `p`.
+struct Blossom {
+    n: usize,
+    edges: Vec<(usize, usize, i64)>,
+    /// Vértice de cada extremidade.
+    endpoint: Vec<usize>,
+    /// Extremidades opostas das arestas incidentes a cada vértice.
+    neighbours: Vec<Vec<usize>>,
+    /// Extremidade remota da aresta emparelhada de cada vértice.
+    mate: Vec<usize>,
+    /// Rótulo de cada vértice ou *blossom* de topo: 0 (livre), 1 (S) ou 2 (T). O bit 4 marca os
+    /// *blossoms* visitados por [`Blossom::scan_blossom`].
+    label: Vec<u8>,
+    /// Extremidade pela qual o vértice ou *blossom* recebeu o rótulo.
+    label_end: Vec<usize>,
+    /// *Blossom* de topo que contém cada vértice.
+    in_blossom: Vec<usize>,
+    parent: Vec<usize>,
+    /// Sub-*blossoms* de cada *blossom*, em ordem cíclica a partir da base.
+    children: Vec<Vec<usize>>,
+    base: Vec<usize>,
+    /// Extremidades das arestas que ligam os sub-*blossoms* consecutivos de cada *blossom*.
+    endps: Vec<Vec<usize>>,
+    /// Aresta de menor folga de cada vértice livre ou *blossom* S até um *blossom* S.
+    best_edge: Vec<usize>,
+    /// Arestas de menor folga de cada *blossom* S até os demais *blossoms* S.
+    blossom_best_edges: Vec<Option<Vec<usize>>>,
+    unused: Vec<usize>,
+    dual: Vec<i64>,
+    /// Arestas de folga zero, que podem ser usadas na busca.
+    allowed: Vec<bool>,
+    queue: Vec<usize>,
+}
+
+impl Blossom {
+    fn new(n: usize, edges: Vec<(usize, usize, i64)>) -> Self {
+        let max_weight = edges.iter().map(|&(_, _, w)| w).max().unwrap_or(0).max(0);
+        let endpoint = edges.iter().flat_map(|&(i, j, _)| [i, j]).collect();
+        let mut neighbours = vec![Vec::new(); n];
+        for (k, &(i, j, _)) in edges.iter().enumerate() {
+            neighbours[i].push(2 * k + 1);
+            neighbours[j].push(2 * k);
+        }
+
+        Self {
+            n,
+            endpoint,
+            neighbours,
+            mate: vec![NONE; n],
+            label: vec![0; 2 * n],
+            label_end: vec![NONE; 2 * n],
+            in_blossom: (0..n).collect(),
+            parent: vec![NONE; 2 * n],
+            children: vec![Vec::new(); 2 * n],
+            base: (0..n).chain(std::iter::repeat_n(NONE, n)).collect(),
+            endps: vec![Vec::new(); 2 * n],
+            best_edge: vec![NONE; 2 * n],
+            blossom_best_edges: vec![None; 2 * n],
+            unused: (n..2 * n).collect(),
+            dual: (0..2 * n)
+                .map(|v| if v < n { max_weight } else { 0 })
+                .collect(),
+            allowed: vec![false; edges.len()],
+            queue: Vec::new(),
+            edges,
+        }
+    }
+
+    fn slack(&self, k: usize) -> i64 {
+        let (i, j, w) = self.edges[k];
+        self.dual[i] + self.dual[j] - 2 * w
+    }
+
+    /// Vértices contidos no *blossom* `b`.
+    fn leaves(&self, b: usize) -> Vec<usize> {
+        let mut leaves = Vec::new();
+        let mut stack = vec![b];
+        while let Some(b) = stack.pop() {
+            if b < self.n {
+                leaves.push(b);
+            } else {
+                stack.extend(self.children[b].iter().rev());
+            }
+        }
+        leaves
+    }
+
+    /// Índice cíclico `j` (possivelmente negativo) na lista de filhos do *blossom* `b`.
+    fn at(&self, b: usize, j: isize) -> usize {
+        j.rem_euclid(self.children[b].len() as isize) as usize
+    }
+
+    /// Rotula `w` e o seu *blossom* com `t`, alcançados pela extremidade `p`. Um *blossom* T
+    /// rotula como S o *blossom* emparelhado com a sua base.
+    fn assign_label(&mut self, w: usize, t: u8, p: usize) {
+        let b = self.in_blossom[w];
+        self.label[w] = t;
+        self.label[b] = t;
+        self.label_end[w] = p;
+        self.label_end[b] = p;
+        self.best_edge[w] = NONE;
+        self.best_edge[b] = NONE;
+        if t == 1 {
+            let leaves = self.leaves(b);
+            self.queue.extend(leaves);
+        } else {
+            let mate = self.mate[self.base[b]];
+            self.assign_label(self.endpoint[mate], 1, mate ^ 1);
+        }
+    }
+
+    /// Sobe pelas árvores alternantes a partir de `v` e `w`. Retorna a base do novo *blossom*,
+    /// caso elas se encontrem, ou [`NONE`] caso haja um caminho de aumento.
+    fn scan_blossom(&mut self, mut v: usize, mut w: usize) -> usize {
+        let mut path = Vec::new();
+        let mut base = NONE;
+        while v != NONE {
+            let mut b = self.in_blossom[v];
+            if self.label[b] & 4 != 0 {
+                base = self.base[b];
+                break;
+            }
+            path.push(b);
+            self.label[b] = 5;
+            if self.label_end[b] == NONE {
+                v = NONE;
+            } else {
+                v = self.endpoint[self.label_end[b]];
+                b = self.in_blossom[v];
+                v = self.endpoint[self.label_end[b]];
+            }
+            if w != NONE {
+                (v, w) = (w, v);
+            }
+        }
+        for b in path {
+            self.label[b] = 1;
+        }
+        base
+    }
+
+    /// Contrai o ciclo fechado pela aresta `k` em um novo *blossom* com base `base`.
+    fn add_blossom(&mut self, base: usize, k: usize) {
+        let (mut v, mut w, _) = self.edges[k];
+        let bb = self.in_blossom[base];
+        let mut bv = self.in_blossom[v];
+        let mut bw = self.in_blossom[w];
+        let b = self.unused.pop().expect("there are at most n / 2 blossoms");
+        self.base[b] = base;
+        self.parent[b] = NONE;
+        self.parent[bb] = b;
+
+        let mut path = Vec::new();
+        let mut endps = Vec::new();
+        while bv != bb {
+            self.parent[bv] = b;
+            path.push(bv);
+            endps.push(self.label_end[bv]);
+            v = self.endpoint[self.label_end[bv]];
+            bv = self.in_blossom[v];
+        }
+        path.push(bb);
+        path.reverse();
+        endps.reverse();
+        endps.push(2 * k);
+        while bw != bb {
+            self.parent[bw] = b;
+            path.push(bw);
+            endps.push(self.label_end[bw] ^ 1);
+            w = self.endpoint[self.label_end[bw]];
+            bw = self.in_blossom[w];
+        }
+
+        self.label[b] = 1;
+        self.label_end[b] = self.label_end[bb];
+        self.dual[b] = 0;
+        for v in self.leaves_of(&path) {
+            if self.label[self.in_blossom[v]] == 2 {
+                self.queue.push(v);
+            }
+            self.in_blossom[v] = b;
+        }
+
+        let mut best_to = vec![NONE; 2 * self.n];
+        for &bv in &path {
+            let lists = match self.blossom_best_edges[bv].take() {
+                Some(list) => vec![list],
+                None => self
+                    .leaves(bv)
+                    .into_iter()
+                    .map(|v| self.neighbours[v].iter().map(|p| p / 2).collect())
+                    .collect(),
+            };
+            for k in lists.into_iter().flatten() {
+                let (i, j, _) = self.edges[k];
+                let j = if self.in_blossom[j] == b { i } else { j };
+                let bj = self.in_blossom[j];
+                if bj != b
+                    && self.label[bj] == 1
+                    && (best_to[bj] == NONE || self.slack(k) < self.slack(best_to[bj]))
+                {
+                    best_to[bj] = k;
+                }
+            }
+            self.best_edge[bv] = NONE;
+        }
+
+        let best_edges: Vec<usize> = best_to.into_iter().filter(|&k| k != NONE).collect();
+        self.best_edge[b] = best_edges
+            .iter()
+            .copied()
+            .min_by_key(|&k| self.slack(k))
+            .unwrap_or(NONE);
+        self.blossom_best_edges[b] = Some(best_edges);
+        self.children[b] = path;
+        self.endps[b] = endps;
+    }
+
+    /// Vértices contidos nos *blossoms* de `blossoms`.
+    fn leaves_of(&self, blossoms: &[usize]) -> Vec<usize> {
+        blossoms.iter().flat_map(|&b| self.leaves(b)).collect()
+    }
+
+    /// Desfaz o *blossom* `b`. Fora do fim de uma etapa, os sub-*blossoms* de um *blossom* T são
+    /// rotulados de forma a manter a árvore alternante.
+    fn expand_blossom(&mut self, b: usize, end_stage: bool) {
+        for s in self.children[b].clone() {
+            self.parent[s] = NONE;
+            if s < self.n {
+                self.in_blossom[s] = s;
+            } else if end_stage && self.dual[s] == 0 {
+                self.expand_blossom(s, end_stage);
+            } else {
+                for v in self.leaves(s) {
+                    self.in_blossom[v] = s;
+                }
+            }
+        }
+
+        if !end_stage && self.label[b] == 2 {
+            let entry = self.in_blossom[self.endpoint[self.label_end[b] ^ 1]];
+            let len = self.children[b].len() as isize;
+            let mut j = self.children[b].iter().position(|&c| c == entry).unwrap() as isize;
+            let (step, trick) = if j & 1 != 0 {
+                j -= len;
+                (1, 0)
+            } else {
+                (-1, 1)
+            };
+
+            let mut p = self.label_end[b];
+            while j != 0 {
+                let q = self.endps[b][self.at(b, j - trick as isize)] ^ trick;
+                self.label[self.endpoint[p ^ 1]] = 0;
+                self.label[self.endpoint[q ^ 1]] = 0;
+                self.assign_label(self.endpoint[p ^ 1], 2, p);
+                self.allowed[q / 2] = true;
+                j += step;
+                p = self.endps[b][self.at(b, j - trick as isize)] ^ trick;
+                self.allowed[p / 2] = true;
+                j += step;
+            }
+
+            let bv = self.children[b][self.at(b, j)];
+            let w = self.endpoint[p ^ 1];
+            self.label[w] = 2;
+            self.label[bv] = 2;
+            self.label_end[w] = p;
+            self.label_end[bv] = p;
+            self.best_edge[bv] = NONE;
+            j += step;
+            while self.children[b][self.at(b, j)] != entry {
+                let bv = self.children[b][self.at(b, j)];
+                j += step;
+                if self.label[bv] == 1 {
+                    continue;
+                }
+                if let Some(v) = self.leaves(bv).into_iter().find(|&v| self.label[v] != 0) {
+                    self.label[v] = 0;
+                    self.label[self.endpoint[self.mate[self.base[bv]]]] = 0;
+                    self.assign_label(v, 2, self.label_end[v]);
+                }
+            }
+        }
+
+        self.label[b] = 0;
+        self.label_end[b] = NONE;
+        self.children[b].clear();
+        self.endps[b].clear();
+        self.base[b] = NONE;
+        self.blossom_best_edges[b] = None;
+        self.best_edge[b] = NONE;
+        self.unused.push(b);
+    }
+
+    /// Troca as arestas emparelhadas dentro do *blossom* `b` ao longo do caminho de `v` até a base,
+    /// que passa a ser `v`.
+    fn augment_blossom(&mut self, b: usize, v: usize) {
+        let mut t = v;
+        while self.parent[t] != b {
+            t = self.parent[t];
+        }
+        if t >= self.n {
+            self.augment_blossom(t, v);
+        }
+
+        let len = self.children[b].len() as isize;
+        let i = self.children[b].iter().position(|&c| c == t).unwrap();
+        let mut j = i as isize;
+        let (step, trick) = if j & 1 != 0 {
+            j -= len;
+            (1, 0)
+        } else {
+            (-1, 1)
+        };
+        while j != 0 {
+            j += step;
+            let t = self.children[b][self.at(b, j)];
+            let p = self.endps[b][self.at(b, j - trick as isize)] ^ trick;
+            if t >= self.n {
+                self.augment_blossom(t, self.endpoint[p]);
+            }
+            j += step;
+            let t = self.children[b][self.at(b, j)];
+            if t >= self.n {
+                self.augment_blossom(t, self.endpoint[p ^ 1]);
+            }
+            self.mate[self.endpoint[p]] = p ^ 1;
+            self.mate[self.endpoint[p ^ 1]] = p;
+        }
+
+        self.children[b].rotate_left(i);
+        self.endps[b].rotate_left(i);
+        self.base[b] = self.base[self.children[b][0]];
+    }
+
+    /// Aumenta o emparelhamento pelo caminho que passa pela aresta `k`.
+    fn augment_matching(&mut self, k: usize) {
+        let (v, w, _) = self.edges[k];
+        for (mut s, mut p) in [(v, 2 * k + 1), (w, 2 * k)] {
+            loop {
+                let bs = self.in_blossom[s];
+                if bs >= self.n {
+                    self.augment_blossom(bs, s);
+                }
+                self.mate[s] = p;
+                if self.label_end[bs] == NONE {
+                    break;
+                }
+                let t = self.endpoint[self.label_end[bs]];
+                let bt = self.in_blossom[t];
+                s = self.endpoint[self.label_end[bt]];
+                let j = self.endpoint[self.label_end[bt] ^ 1];
+                if bt >= self.n {
+                    self.augment_blossom(bt, j);
+                }
+                self.mate[j] = self.label_end[bt];
+                p = self.label_end[bt] ^ 1;
+            }
+        }
+    }
+
+    /// Executa as etapas do algoritmo, cada uma aumentando o emparelhamento em uma aresta, até que
+    /// não haja mais caminhos de aumento. Retorna o par de cada vértice ([`NONE`] se livre).
+    fn solve(mut self) -> Vec<usize> {
+        let n = self.n;
+        for _ in 0..n {
+            self.label.fill(0);
+            self.best_edge.fill(NONE);
+            self.blossom_best_edges[n..].fill(None);
+            self.allowed.fill(false);
+            self.queue.clear();
+            for v in 0..n {
+                if self.mate[v] == NONE && self.label[self.in_blossom[v]] == 0 {
+                    self.assign_label(v, 1, NONE);
+                }
+            }
+
+            let mut augmented = false;
+            loop {
+                while !augmented && let Some(v) = self.queue.pop() {
+                    for p in self.neighbours[v].clone() {
+                        let k = p / 2;
+                        let w = self.endpoint[p];
+                        if self.in_blossom[v] == self.in_blossom[w] {
+                            continue;
+                        }
+                        let mut slack = 0;
+                        if !self.allowed[k] {
+                            slack = self.slack(k);
+                            if slack <= 0 {
+                                self.allowed[k] = true;
+                            }
+                        }
+
+                        if self.allowed[k] {
+                            if self.label[self.in_blossom[w]] == 0 {
+                                self.assign_label(w, 2, p ^ 1);
+                            } else if self.label[self.in_blossom[w]] == 1 {
+                                let base = self.scan_blossom(v, w);
+                                if base != NONE {
+                                    self.add_blossom(base, k);
+                                } else {
+                                    self.augment_matching(k);
+                                    augmented = true;
+                                    break;
+                                }
+                            } else if self.label[w] == 0 {
+                                self.label[w] = 2;
+                                self.label_end[w] = p ^ 1;
+                            }
+                        } else if self.label[self.in_blossom[w]] == 1 {
+                            let b = self.in_blossom[v];
+                            if self.best_edge[b] == NONE || slack < self.slack(self.best_edge[b]) {
+                                self.best_edge[b] = k;
+                            }
+                        } else if self.label[w] == 0
+                            && (self.best_edge[w] == NONE || slack < self.slack(self.best_edge[w]))
+                        {
+                            self.best_edge[w] = k;
+                        }
+                    }
+                }
+                if augmented {
+                    break;
+                }
+
+                // Nenhum caminho de aumento com as arestas permitidas: ajusta as variáveis duais.
+                let mut delta: Option<(i64, u8, usize)> = None;
+                let mut consider = |d: i64, kind: u8, item: usize| {
+                    if delta.is_none_or(|(best, _, _)| d < best) {
+                        delta = Some((d, kind, item));
+                    }
+                };
+                for v in 0..n {
+                    if self.label[self.in_blossom[v]] == 0 && self.best_edge[v] != NONE {
+                        consider(self.slack(self.best_edge[v]), 2, self.best_edge[v]);
+                    }
+                }
+                for b in 0..2 * n {
+                    if self.parent[b] == NONE && self.label[b] == 1 && self.best_edge[b] != NONE {
+                        let slack = self.slack(self.best_edge[b]);
+                        debug_assert!(slack % 2 == 0);
+                        consider(slack / 2, 3, self.best_edge[b]);
+                    }
+                }
+                for b in n..2 * n {
+                    if self.base[b] != NONE && self.parent[b] == NONE && self.label[b] == 2 {
+                        consider(self.dual[b], 4, b);
+                    }
+                }
+                let (delta, kind, item) = delta.unwrap_or_else(|| {
+                    let min_dual = self.dual[..n].iter().copied().min().unwrap_or(0);
+                    (min_dual.max(0), 1, NONE)
+                });
+
+                for v in 0..n {
+                    match self.label[self.in_blossom[v]] {
+                        1 => self.dual[v] -= delta,
+                        2 => self.dual[v] += delta,
+                        _ => {}
+                    }
+                }
+                for b in n..2 * n {
+                    if self.base[b] != NONE && self.parent[b] == NONE {
+                        match self.label[b] {
+                            1 => self.dual[b] += delta,
+                            2 => self.dual[b] -= delta,
+                            _ => {}
+                        }
+                    }
+                }
+
+                match kind {
+                    1 => break,
+                    2 => {
+                        self.allowed[item] = true;
+                        let (i, j, _) = self.edges[item];
+                        let i = if self.label[self.in_blossom[i]] == 0 {
+                            j
+                        } else {
+                            i
+                        };
+                        self.queue.push(i);
+                    }
+                    3 => {
+                        self.allowed[item] = true;
+                        self.queue.push(self.edges[item].0);
+                    }
+                    _ => self.expand_blossom(item, false),
+                }
+            }
+
+            if !augmented {
+                break;
+            }
+            for b in n..2 * n {
+                if self.parent[b] == NONE
+                    && self.base[b] != NONE
+                    && self.label[b] == 1
+                    && self.dual[b] == 0
+                {
+                    self.expand_blossom(b, true);
+                }
+            }
+        }
+
+        self.mate
+            .iter()
+            .map(|&p| if p == NONE { NONE } else { self.endpoint[p] })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+
+    use super::*;
+    use crate::matrix::Matrix;
+
+    /// Custo mínimo de um emparelhamento perfeito por força bruta, sempre emparelhando o primeiro
+    /// vértice livre.
+    fn brute_force(graph: &Matrix, free: &mut Vec<usize>) -> f64 {
+        let Some(a) = free.pop() else {
+            return 0.0;
+        };
+        let mut best = f64::INFINITY;
+        for i in 0..free.len() {
+            let b = free.remove(i);
+            best = best.min(graph[a][b] + brute_force(graph, free));
+            free.insert(i, b);
+        }
+        free.push(a);
+        best
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for k in (2..=12).step_by(2) {
+            for round in 0..30 {
+                let mut graph = Matrix::new(k);
+                for a in 0..k {
+                    for b in a + 1..k {
+                        // Alguns rounds usam poucos valores distintos, forçando empates.
+                        let w = if round % 3 == 0 {
+                            rng.random_range(1..4) as f64
+                        } else {
+                            (rng.random_range(1.0..100.0_f64) * 10.0).round() / 10.0
+                        };
+                        graph[a][b] = w;
+                        graph[b][a] = w;
+                    }
+                }
+
+                let nodes: Vec<usize> = (0..k).collect();
+                let pairs = minimum_matching(&graph, &nodes);
+                let mut seen = vec![false; k];
+                for &(a, b) in &pairs {
+                    assert!(!seen[a] && !seen[b], "{pairs:?} is not a matching");
+                    seen[a] = true;
+                    seen[b] = true;
+                }
+                assert_eq!(pairs.len(), k / 2, "{pairs:?} is not perfect");
+
+                let cost: f64 = pairs.iter().map(|&(a, b)| graph[a][b]).sum();
+                let expected = brute_force(&graph, &mut nodes.clone());
+                assert!(
+                    (cost - expected).abs() < 1e-6,
+                    "cost {cost}, expected {expected}"
+                );
+            }
+        }
+    }
+}
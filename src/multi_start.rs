@@ -0,0 +1,89 @@
+//! Execução de uma heurística (construtiva, seguida ou não de busca local) a partir de vários
+//! vértices iniciais.
+
+use rand::{Rng, seq::index};
+
+use crate::local_search::Solution;
+
+/// Resumo de uma execução com múltiplos inícios.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultiStart {
+    /// Melhor rota encontrada.
+    pub best: Solution,
+    /// Vértice inicial que gerou a melhor rota.
+    pub best_start: usize,
+    /// Custo médio das rotas encontradas.
+    pub mean: f64,
+    /// Custo da pior rota encontrada.
+    pub worst: f64,
+    /// Quantidade de vértices iniciais usados.
+    pub runs: usize,
+}
+
+/// Executa `pipeline` a partir de cada vértice em `starts`.
+///
+/// # Argumentos
+/// - `graph`: grafo repassado ao `pipeline`;
+/// - `starts`: vértices iniciais, e.g. `0..n` para todos os vértices ou o resultado de
+///   [`sample_starts`];
+/// - `pipeline`: função que constrói (e possivelmente melhora) uma rota a partir de um vértice.
+///
+/// # Saída
+/// Retorna a melhor rota, o vértice inicial que a gerou e os custos médio e pior. Em caso de
+/// empate, fica o primeiro vértice inicial a alcançar o melhor custo.
+///
+/// # Panics
+/// Caso `starts` seja vazio.
+pub fn multi_start<G, F>(
+    graph: &G,
+    starts: impl IntoIterator<Item = usize>,
+    mut pipeline: F,
+) -> MultiStart
+where
+    G: ?Sized,
+    F: FnMut(&G, usize) -> Solution,
+{
+    let mut summary: Option<MultiStart> = None;
+    let mut total = 0.0;
+
+    for start in starts {
+        let solution = pipeline(graph, start);
+        total += solution.cost;
+
+        match &mut summary {
+            None => {
+                summary = Some(MultiStart {
+                    worst: solution.cost,
+                    best: solution,
+                    best_start: start,
+                    mean: 0.0,
+                    runs: 1,
+                })
+            }
+            Some(s) => {
+                s.runs += 1;
+                s.worst = s.worst.max(solution.cost);
+                if solution.cost < s.best.cost {
+                    s.best = solution;
+                    s.best_start = start;
+                }
+            }
+        }
+    }
+
+    let mut summary = summary.expect("At least one start node is required");
+    summary.mean = total / summary.runs as f64;
+    summary
+}
+
+/// Sorteia, sem repetição, `count` vértices iniciais dentre os `n` vértices do grafo. Caso
+/// `count >= n`, retorna todos os vértices.
+pub fn sample_starts<R: Rng + ?Sized>(n: usize, count: usize, rng: &mut R) -> Vec<usize> {
+    if count >= n {
+        return (0..n).collect();
+    }
+
+    let mut starts = index::sample(rng, n, count).into_vec();
+    starts.sort_unstable();
+    starts
+}
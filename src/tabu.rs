@@ -0,0 +1,254 @@
+//! Busca Tabu (*Tabu Search*) para o Problema do Caixeiro Viajante, sobre as vizinhanças 2-opt e
+//! swap.
+//!
+//! Os atributos tabu são arestas: ao aplicar um movimento, as arestas que ele removeu ficam
+//! proibidas de voltar à rota por `tenure` iterações.
+
+use std::{fmt, str::FromStr, time::Instant};
+
+use crate::{
+    budget::Budget,
+    local_search::{
+        EPSILON, Solution,
+        moves::{Move, Swap, TwoOpt},
+    },
+    matrix::DistanceMatrix,
+};
+
+/// Vizinhança explorada pela busca tabu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveKind {
+    Swap,
+    TwoOpt,
+}
+
+impl MoveKind {
+    /// Lê uma lista de vizinhanças separadas por vírgula, e.g. `2opt,swap`.
+    pub fn parse_list(s: &str) -> Result<Vec<Self>, String> {
+        s.split(',').map(str::parse).collect()
+    }
+}
+
+impl FromStr for MoveKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "swap" => Ok(MoveKind::Swap),
+            "2opt" => Ok(MoveKind::TwoOpt),
+            s => Err(format!("unknown move: {s}")),
+        }
+    }
+}
+
+impl fmt::Display for MoveKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoveKind::Swap => write!(f, "swap"),
+            MoveKind::TwoOpt => write!(f, "2-opt"),
+        }
+    }
+}
+
+/// Aresta `(u, v)` da rota, dada pelos seus vértices.
+type Edge = (usize, usize);
+
+/// Movimento candidato de uma das vizinhanças de [`MoveKind`].
+enum Candidate {
+    Swap(Swap),
+    TwoOpt(TwoOpt),
+}
+
+impl Candidate {
+    /// Arestas removidas e adicionadas pelo movimento, sem as que aparecem em ambas.
+    fn edges(&self, route: &[usize]) -> (Vec<Edge>, Vec<Edge>) {
+        let n = route.len();
+        let (mut removed, mut added) = match *self {
+            Candidate::TwoOpt(TwoOpt { i, j }) => (
+                vec![(route[i], route[i + 1]), (route[j], route[(j + 1) % n])],
+                vec![(route[i], route[j]), (route[i + 1], route[(j + 1) % n])],
+            ),
+            Candidate::Swap(Swap { i, j }) => {
+                let after = |p: usize| match p {
+                    p if p == i => route[j],
+                    p if p == j => route[i],
+                    p => route[p],
+                };
+                let mut positions = [(i + n - 1) % n, i, (j + n - 1) % n, j];
+                positions.sort_unstable();
+
+                let mut removed = Vec::with_capacity(4);
+                let mut added = Vec::with_capacity(4);
+                for (k, &p) in positions.iter().enumerate() {
+                    if k == 0 || positions[k - 1] != p {
+                        removed.push((route[p], route[(p + 1) % n]));
+                        added.push((after(p), after((p + 1) % n)));
+                    }
+                }
+                (removed, added)
+            }
+        };
+
+        let same =
+            |(a, b): (usize, usize), (u, v): (usize, usize)| (a, b) == (u, v) || (a, b) == (v, u);
+        let kept: Vec<Edge> = removed
+            .iter()
+            .copied()
+            .filter(|&e| added.iter().any(|&f| same(e, f)))
+            .collect();
+        removed.retain(|&e| !kept.iter().any(|&f| same(e, f)));
+        added.retain(|&e| !kept.iter().any(|&f| same(e, f)));
+
+        (removed, added)
+    }
+}
+
+impl Move for Candidate {
+    fn delta<M: DistanceMatrix + ?Sized>(&self, route: &[usize], graph: &M) -> f64 {
+        match self {
+            Candidate::Swap(mv) => mv.delta(route, graph),
+            Candidate::TwoOpt(mv) => mv.delta(route, graph),
+        }
+    }
+
+    fn apply(&self, route: &mut Vec<usize>) {
+        match self {
+            Candidate::Swap(mv) => mv.apply(route),
+            Candidate::TwoOpt(mv) => mv.apply(route),
+        }
+    }
+
+    fn asymmetric_delta<M: DistanceMatrix + ?Sized>(&self, route: &[usize], graph: &M) -> f64 {
+        match self {
+            Candidate::Swap(mv) => mv.asymmetric_delta(route, graph),
+            Candidate::TwoOpt(mv) => mv.asymmetric_delta(route, graph),
+        }
+    }
+}
+
+/// Parâmetros da busca tabu.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tabu {
+    /// Vizinhanças exploradas a cada iteração.
+    pub moves: Vec<MoveKind>,
+    /// Quantidade de iterações em que uma aresta removida fica proibida de voltar à rota.
+    pub tenure: usize,
+    /// Peso da memória de longo prazo. Com `0`, não há diversificação por frequência.
+    pub frequency_weight: f64,
+    /// Critério de parada, em quantidade de iterações ou tempo.
+    pub budget: Budget,
+}
+
+/// Resultado da busca tabu.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TabuResult {
+    pub best: Solution,
+    /// Custo da melhor solução ao fim de cada iteração.
+    pub history: Vec<f64>,
+}
+
+impl Tabu {
+    /// Executa a busca tabu a partir de `initial`.
+    ///
+    /// # Detalhes de funcionamento
+    /// 1. Em cada iteração, avalia todos os movimentos das vizinhanças e aplica o de menor custo
+    ///    dentre os permitidos, mesmo que piore a solução. Um movimento é tabu se adiciona alguma
+    ///    aresta removida há menos de `tenure` iterações;
+    /// 2. Critério de aspiração: um movimento tabu é permitido se levar a uma solução melhor que a
+    ///    melhor já encontrada. Caso todos os movimentos sejam tabu e nenhum satisfaça a aspiração,
+    ///    aplica-se o de menor custo;
+    /// 3. Com memória de longo prazo, movimentos que não melhoram a solução são penalizados em
+    ///    `frequency_weight · (custo médio de uma aresta da rota) · f`, onde `f` é a soma, sobre
+    ///    as arestas adicionadas, da fração das iterações em que cada uma já foi adicionada. Isso
+    ///    afasta a busca de arestas muito usadas;
+    /// 4. Repete até o fim do orçamento, registrando o custo da melhor solução a cada iteração.
+    ///
+    /// Observação: as arestas são tratadas como não direcionadas nos atributos tabu e na
+    /// memória de frequência, mesmo em matrizes assimétricas.
+    pub fn run<M: DistanceMatrix + ?Sized>(&self, initial: &Solution, graph: &M) -> TabuResult {
+        let n = initial.route.len();
+        let symmetric = graph.is_symmetric();
+        let key = |(u, v): Edge| u.min(v) * n + u.max(v);
+
+        let mut current = initial.clone();
+        let mut best = initial.clone();
+        let mut history = Vec::new();
+        let mut tabu_until = vec![0; n * n];
+        let mut frequency = vec![0usize; n * n];
+        let started = Instant::now();
+        let mut iteration = 0;
+
+        while n >= 4 && !self.budget.exhausted(iteration, started) {
+            iteration += 1;
+            let edge_cost = current.cost / n as f64;
+            let mut chosen: Option<(Candidate, f64, f64)> = None;
+            let mut fallback: Option<(Candidate, f64)> = None;
+
+            for mv in self.candidates(n) {
+                let (_, added) = mv.edges(&current.route);
+                if added.is_empty() {
+                    continue;
+                }
+
+                let delta = current.evaluate(graph, symmetric, &mv);
+                let is_tabu = added.iter().any(|&e| tabu_until[key(e)] > iteration);
+                let aspires = current.cost + delta < best.cost - EPSILON;
+
+                if is_tabu && !aspires {
+                    if fallback.as_ref().is_none_or(|&(_, d)| delta < d) {
+                        fallback = Some((mv, delta));
+                    }
+                    continue;
+                }
+
+                let penalty = if self.frequency_weight > 0.0 && delta >= 0.0 {
+                    let f: usize = added.iter().map(|&e| frequency[key(e)]).sum();
+                    self.frequency_weight * edge_cost * f as f64 / iteration as f64
+                } else {
+                    0.0
+                };
+                if chosen.as_ref().is_none_or(|&(_, _, s)| delta + penalty < s) {
+                    chosen = Some((mv, delta, delta + penalty));
+                }
+            }
+
+            let Some((mv, delta)) = chosen.map(|(mv, delta, _)| (mv, delta)).or(fallback) else {
+                break;
+            };
+
+            let (removed, added) = mv.edges(&current.route);
+            for e in removed {
+                tabu_until[key(e)] = iteration + self.tenure + 1;
+            }
+            for e in added {
+                frequency[key(e)] += 1;
+            }
+
+            current.apply(&mv, delta);
+            if current.cost < best.cost - EPSILON {
+                best = current.clone();
+            }
+            history.push(best.cost);
+        }
+
+        // Recalcula o custo para não acumular os erros de arredondamento das variações.
+        best.cost = Solution::calculate_cost(&best.route, graph);
+        TabuResult { best, history }
+    }
+
+    /// Todos os movimentos das vizinhanças escolhidas para uma rota com `n` vértices.
+    fn candidates(&self, n: usize) -> impl Iterator<Item = Candidate> + use<'_> {
+        self.moves
+            .iter()
+            .flat_map(move |kind| -> Box<dyn Iterator<Item = Candidate>> {
+                match kind {
+                    MoveKind::Swap => Box::new((0..n).flat_map(move |i| {
+                        (i + 1..n).map(move |j| Candidate::Swap(Swap { i, j }))
+                    })),
+                    MoveKind::TwoOpt => Box::new((0..n - 2).flat_map(move |i| {
+                        (i + 2..n).map(move |j| Candidate::TwoOpt(TwoOpt { i, j }))
+                    })),
+                }
+            })
+    }
+}